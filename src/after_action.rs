@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use crate::character::CharacterStatus;
+use crate::game::Game;
+use crate::snapshot::{Side, UnitKind, WorldSnapshot};
+use crate::vec2d;
+
+const SAMPLE_INTERVAL: f64 = 1.0;
+
+// running totals the game keeps as it goes, the rest of the report is read off the end state
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Statistics {
+    pub distance_travelled: f64,
+    pub scout_missions_sent: u32,
+    pub scout_missions_returned: u32,
+    pub orders_given: u32,
+    pub intel_ages_at_orders: Vec<f64>
+}
+
+pub struct AfterActionReport {
+    pub time: f64,
+    pub distance_travelled: f64,
+    pub scout_missions_sent: u32,
+    pub scout_missions_returned: u32,
+    pub orders_given: u32,
+    pub orders_without_intel: u32,
+    pub mean_intel_age: Option<f64>,
+    pub mean_order_latency: Option<f64>,
+    pub soldiers_lost: u32,
+    pub enemy_soldiers_killed: u32
+}
+
+impl AfterActionReport {
+
+    pub fn new(snapshot: &WorldSnapshot) -> Self {
+        let statistics = &snapshot.statistics;
+        let intel_ages = &statistics.intel_ages_at_orders;
+
+        Self {
+            time: snapshot.time,
+            distance_travelled: statistics.distance_travelled,
+            scout_missions_sent: statistics.scout_missions_sent,
+            scout_missions_returned: statistics.scout_missions_returned,
+            orders_given: statistics.orders_given,
+            orders_without_intel: statistics.orders_given - intel_ages.len() as u32,
+            mean_intel_age: if intel_ages.is_empty() { None } else { Some(intel_ages.iter().sum::<f64>() / intel_ages.len() as f64) },
+            mean_order_latency: snapshot.average_order_latency,
+            soldiers_lost: snapshot.friendly_casualties,
+            enemy_soldiers_killed: snapshot.enemy_casualties
+        }
+    }
+}
+
+// where the enemy really was next to where the commander believed it was
+#[derive(Clone)]
+pub struct TimelineSample {
+    pub time: f64,
+    // none once the enemy has been destroyed
+    pub enemy_position: Option<vec2d::Vec2D>,
+    pub believed_enemy_position: Option<vec2d::Vec2D>,
+    pub believed_at: Option<f64>,
+    pub friendly_positions: Vec<vec2d::Vec2D>
+}
+
+pub struct Timeline {
+    samples: Vec<TimelineSample>,
+    next_sample_time: f64,
+    is_finished: bool
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timeline {
+
+    pub fn new() -> Self {
+        Self {
+            samples: Vec::new(),
+            next_sample_time: 0.0,
+            is_finished: false
+        }
+    }
+
+    // takes a sample once per SAMPLE_INTERVAL of game time and one of the final state, then stops
+    pub fn record(&mut self, game: &Game) {
+        if self.is_finished || (game.get_time() < self.next_sample_time && !game.is_over()) {
+            return;
+        }
+        let snapshot = game.omniscient_snapshot();
+        self.samples.push(TimelineSample {
+            time: snapshot.time,
+            enemy_position: snapshot.get_units_of(Side::Enemy).find(|enemy| enemy.status != CharacterStatus::Dead).map(|enemy| enemy.position),
+            believed_enemy_position: snapshot.enemy_intel.map(|intel| intel.position),
+            believed_at: snapshot.enemy_intel.map(|intel| intel.observed_at),
+            friendly_positions: snapshot.get_units_of(Side::Friendly).filter(|unit| unit.kind == UnitKind::Troops).map(|unit| unit.position).collect()
+        });
+        self.next_sample_time = snapshot.time + SAMPLE_INTERVAL;
+        self.is_finished = game.is_over();
+    }
+
+    pub fn get_samples(&self) -> &[TimelineSample] {
+        &self.samples
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+use clap::Args;
+use clap::builder::PossibleValuesParser;
+use crate::config::{ConfigError, GameConfig};
+use crate::scenario;
+
+// how a match is set up, the same for the game and the simulator
+#[derive(Args)]
+pub struct GameArgs {
+    /// Starting layout to play
+    #[arg(long, default_value = scenario::DEFAULT_SCENARIO, value_parser = PossibleValuesParser::new(scenario::SCENARIOS.map(|scenario| scenario.name)))]
+    pub scenario: String,
+
+    /// Config file with the game tunables [default: config.json if present]
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Override one config setting, can be repeated
+    #[arg(long = "set", value_name = "NAME=VALUE")]
+    pub overrides: Vec<String>
+}
+
+impl GameArgs {
+
+    // the parser only lets known scenario names through
+    pub fn get_scenario(&self) -> &'static scenario::Scenario {
+        scenario::find_scenario(&self.scenario).unwrap()
+    }
+
+    pub fn load_config(&self) -> Result<GameConfig, ConfigError> {
+        GameConfig::load_with_overrides(self.config.as_deref(), &self.overrides)
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::PathBuf;
+use clap::{Parser, ValueEnum};
+use clap::builder::PossibleValuesParser;
+use war_fog_experiment::args::GameArgs;
+use war_fog_experiment::simulation::{self, MatchResult, StrategySummary};
+use war_fog_experiment::strategy::{self, Strategy};
+
+#[derive(Copy, Clone, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Csv,
+    Json
+}
+
+/// Runs seeded matches with scripted player strategies and reports how each one fares
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// Matches per strategy, match i uses seed + i for every strategy
+    #[arg(long, default_value_t = 1000)]
+    matches: u64,
+
+    /// First seed
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Strategies to compare
+    #[arg(long, value_delimiter = ',', default_values = strategy::STRATEGY_KINDS, value_parser = PossibleValuesParser::new(strategy::STRATEGY_KINDS))]
+    strategies: Vec<String>,
+
+    /// Seconds between scout missions for scout-every
+    #[arg(long, value_name = "SECONDS", default_value_t = 30.0)]
+    scout_interval: f64,
+
+    /// Game time in seconds after which a match counts as undecided
+    #[arg(long, value_name = "SECONDS", default_value_t = 900.0)]
+    duration: f64,
+
+    /// Simulation step in seconds
+    #[arg(long, value_name = "SECONDS", default_value_t = 0.05, value_parser = parse_positive_seconds)]
+    dt: f64,
+
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Csv)]
+    format: OutputFormat,
+
+    /// Write one row per match instead of one per strategy
+    #[arg(long)]
+    per_match: bool,
+
+    /// Output file, standard output if not given
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>
+}
+
+fn parse_positive_seconds(value: &str) -> Result<f64, String> {
+    let seconds: f64 = value.parse().map_err(|_| format!("{value} is not a number"))?;
+    if seconds > 0.0 {
+        Ok(seconds)
+    } else {
+        Err("must be greater than 0".to_string())
+    }
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    value.map(|value| format!("{value:.2}")).unwrap_or_default()
+}
+
+fn to_csv_matches(results: &[MatchResult]) -> String {
+    let mut csv = "strategy,seed,outcome,duration,time_to_contact,mean_intel_age,mean_order_latency\n".to_string();
+    for result in results.iter() {
+        csv += &format!("{},{},{},{:.2},{},{},{}\n", result.strategy, result.seed, simulation::format_outcome(result.outcome), result.duration,
+            format_optional(result.time_to_contact), format_optional(result.mean_intel_age), format_optional(result.mean_order_latency));
+    }
+    csv
+}
+
+fn to_csv_summaries(summaries: &[StrategySummary]) -> String {
+    let mut csv = "strategy,matches,victories,defeats,undecided,win_rate,contact_rate,mean_time_to_contact,mean_intel_age,mean_order_latency\n".to_string();
+    for summary in summaries.iter() {
+        csv += &format!("{},{},{},{},{},{:.4},{:.4},{},{},{}\n", summary.strategy, summary.matches, summary.victories, summary.defeats, summary.undecided,
+            summary.win_rate, summary.contact_rate, format_optional(summary.mean_time_to_contact), format_optional(summary.mean_intel_age),
+            format_optional(summary.mean_order_latency));
+    }
+    csv
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let game_config = cli.game.load_config().unwrap_or_else(|error| {
+        eprintln!("Could not load config: {error}");
+        std::process::exit(1);
+    });
+    let scenario = cli.game.get_scenario();
+
+    let mut all_results = Vec::new();
+    let mut summaries = Vec::new();
+    for kind in cli.strategies.iter() {
+        let strategy = Strategy::from_kind(kind, cli.scout_interval).unwrap();
+        let results: Vec<MatchResult> = (0..cli.matches)
+            .map(|index| simulation::run_match(scenario, &game_config, strategy, cli.seed.wrapping_add(index), cli.dt, cli.duration))
+            .collect();
+        summaries.push(simulation::summarize(strategy, &results));
+        all_results.extend(results);
+    }
+
+    let output = match (cli.format, cli.per_match) {
+        (OutputFormat::Csv, true) => to_csv_matches(&all_results),
+        (OutputFormat::Csv, false) => to_csv_summaries(&summaries),
+        (OutputFormat::Json, true) => serde_json::to_string_pretty(&all_results).unwrap(),
+        (OutputFormat::Json, false) => serde_json::to_string_pretty(&summaries).unwrap()
+    };
+
+    match &cli.output {
+        Some(path) => fs::write(path, output).unwrap_or_else(|error| {
+            eprintln!("Could not write {}: {error}", path.display());
+            std::process::exit(1);
+        }),
+        None => print!("{output}")
+    }
+}
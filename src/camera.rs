@@ -0,0 +1,146 @@
+use war_fog_experiment::vec2d;
+
+const EDGE_SCROLL_MARGIN: f64 = 15.0;
+const ZOOM_STEP: f64 = 1.1;
+const MIN_ZOOM: f64 = 0.4;
+const MAX_ZOOM: f64 = 3.0;
+
+pub struct Camera {
+    position: vec2d::Vec2D,
+    speed: vec2d::Vec2D,
+    viewport_center: vec2d::Vec2D,
+    zoom: f64,
+    map_size: vec2d::Vec2D,
+    move_speed: f64,
+    is_following: bool
+}
+
+impl Camera {
+
+    pub fn new(map_size: vec2d::Vec2D, move_speed: f64) -> Self {
+        Self {
+            position: vec2d::new(),
+            speed: vec2d::new(),
+            viewport_center: vec2d::new(),
+            zoom: 1.0,
+            map_size,
+            move_speed,
+            is_following: false
+        }
+    }
+
+    // cursor_position is None when the cursor is outside the window, so it does not edge scroll
+    pub fn update(&mut self, dt: &f64, cursor_position: Option<&vec2d::Vec2D>, follow_target: Option<vec2d::Vec2D>) {
+        let edge_speed = cursor_position.map(|cursor| self.get_edge_scroll_speed(cursor)).unwrap_or(vec2d::new());
+        let speed = vec2d::Vec2D {
+            x: num::clamp(self.speed.x + edge_speed.x, -self.move_speed, self.move_speed),
+            y: num::clamp(self.speed.y + edge_speed.y, -self.move_speed, self.move_speed)
+        };
+
+        if speed.x != 0.0 || speed.y != 0.0 {
+            self.is_following = false;
+        }
+
+        match follow_target {
+            Some(target) if self.is_following => self.position = target,
+            _ => {
+                // pan at a constant screen speed whatever the zoom
+                self.position.x += speed.x * dt / self.zoom;
+                self.position.y += speed.y * dt / self.zoom;
+            }
+        }
+        self.clamp_to_map();
+    }
+
+    fn get_edge_scroll_speed(&self, cursor_position: &vec2d::Vec2D) -> vec2d::Vec2D {
+        let mut speed = vec2d::new();
+        if cursor_position.x <= EDGE_SCROLL_MARGIN {
+            speed.x = -self.move_speed;
+        } else if cursor_position.x >= self.viewport_center.x * 2.0 - EDGE_SCROLL_MARGIN {
+            speed.x = self.move_speed;
+        }
+        if cursor_position.y <= EDGE_SCROLL_MARGIN {
+            speed.y = -self.move_speed;
+        } else if cursor_position.y >= self.viewport_center.y * 2.0 - EDGE_SCROLL_MARGIN {
+            speed.y = self.move_speed;
+        }
+        speed
+    }
+
+    // keeps the view inside the map, centring the map when it is smaller than the view
+    fn clamp_to_map(&mut self) {
+        let half_view_x = self.viewport_center.x / self.zoom;
+        let half_view_y = self.viewport_center.y / self.zoom;
+        let max_x = self.map_size.x / 2.0 - half_view_x;
+        let max_y = self.map_size.y / 2.0 - half_view_y;
+        self.position.x = if max_x > 0.0 { num::clamp(self.position.x, -max_x, max_x) } else { 0.0 };
+        self.position.y = if max_y > 0.0 { num::clamp(self.position.y, -max_y, max_y) } else { 0.0 };
+    }
+
+    pub fn pan(&mut self, direction: &vec2d::Vec2D) {
+        self.speed.x = num::clamp(self.speed.x + direction.x * self.move_speed, -self.move_speed, self.move_speed);
+        self.speed.y = num::clamp(self.speed.y + direction.y * self.move_speed, -self.move_speed, self.move_speed);
+    }
+
+    pub fn stop_horizontal_pan(&mut self) {
+        self.speed.x = 0.0;
+    }
+
+    pub fn stop_vertical_pan(&mut self) {
+        self.speed.y = 0.0;
+    }
+
+    // the world point under the cursor stays under the cursor
+    pub fn zoom_at(&mut self, screen_position: &vec2d::Vec2D, steps: f64) {
+        let world_position = self.to_world_position(screen_position);
+        self.zoom = num::clamp(self.zoom * ZOOM_STEP.powf(steps), MIN_ZOOM, MAX_ZOOM);
+        self.position.x = world_position.x - (screen_position.x - self.viewport_center.x) / self.zoom;
+        self.position.y = world_position.y - (screen_position.y - self.viewport_center.y) / self.zoom;
+        self.clamp_to_map();
+    }
+
+    pub fn center_on(&mut self, position: &vec2d::Vec2D) {
+        self.position = *position;
+        self.is_following = false;
+        self.clamp_to_map();
+    }
+
+    pub fn toggle_follow(&mut self) {
+        self.is_following = !self.is_following;
+    }
+
+    pub fn set_viewport_size(&mut self, window_size: &[f64; 2]) {
+        self.viewport_center.x = window_size[0] / 2.0;
+        self.viewport_center.y = window_size[1] / 2.0;
+    }
+
+    pub fn get_viewport_center(&self) -> &vec2d::Vec2D {
+        &self.viewport_center
+    }
+
+    pub fn get_viewport_size(&self) -> vec2d::Vec2D {
+        vec2d::Vec2D{ x: self.viewport_center.x * 2.0, y: self.viewport_center.y * 2.0 }
+    }
+
+    pub fn get_position(&self) -> &vec2d::Vec2D {
+        &self.position
+    }
+
+    pub fn get_zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    pub fn to_world_position(&self, screen_position: &vec2d::Vec2D) -> vec2d::Vec2D {
+        vec2d::Vec2D {
+            x: (screen_position.x - self.viewport_center.x) / self.zoom + self.position.x,
+            y: (screen_position.y - self.viewport_center.y) / self.zoom + self.position.y
+        }
+    }
+
+    pub fn to_screen_position(&self, world_position: &vec2d::Vec2D) -> vec2d::Vec2D {
+        vec2d::Vec2D {
+            x: (world_position.x - self.position.x) * self.zoom + self.viewport_center.x,
+            y: (world_position.y - self.position.y) * self.zoom + self.viewport_center.y
+        }
+    }
+}
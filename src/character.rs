@@ -1,27 +1,61 @@
+use serde::{Deserialize, Serialize};
 use crate::vec2d;
 use crate::collision;
+use crate::config;
 
-const CHARACTER_SPEED: f64 = 50.0;
+pub const CHARACTER_SPEED: f64 = 50.0;
+const DEFAULT_SOLDIERS: u32 = 100;
+pub const MAX_STAMINA: f64 = 100.0;
+const MIN_VIEW_FACTOR: f64 = 0.5;
+const MAX_MORALE: f64 = 100.0;
+const MORALE_RECOVERY: f64 = 1.0;
+const CASUALTY_MORALE_FACTOR: f64 = 200.0;
+const ROUT_MORALE: f64 = 25.0;
+const RALLY_MORALE: f64 = 60.0;
+const ROUT_DISTANCE: f64 = 400.0;
+const ROUT_SPEED_FACTOR: f64 = 1.5;
+const CONCEALED_VIEW_FACTOR: f64 = 0.5;
+const SURPRISE_DURATION: f64 = 10.0;
 
-#[derive(PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum CharacterStatus {
     Idle,
     Moving,
-    Fighting
+    Fighting,
+    Routing,
+    Dead
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Character {
     position: vec2d::Vec2D,
     speed: vec2d::Vec2D,
     target_position: vec2d::Vec2D,
+    heading: vec2d::Vec2D,
     max_speed: f64,
+    view_radius: f64,
+    touch_radius: f64,
     is_visible: bool,
     known_enemy_position: vec2d::Vec2D,
+    known_enemy_time: f64,
+    last_report_time: Option<f64>,
     has_discovered_enemy: bool,
-    health: f64,
+    is_in_contact: bool,
+    soldiers: u32,
+    max_soldiers: u32,
+    pending_casualties: f64,
+    stamina: f64,
+    morale: f64,
+    surprised_time: f64,
     status: CharacterStatus
 }
 
+impl Default for Character {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Character {
 
     pub fn new() -> Self{
@@ -29,51 +63,131 @@ impl Character {
             position: vec2d::new(),
             speed: vec2d::new(),
             target_position: vec2d::new(),
+            heading: vec2d::new(),
             max_speed: CHARACTER_SPEED,
+            view_radius: collision::VIEW_RADIUS,
+            touch_radius: collision::TOUCH_RADIUS,
             is_visible: false,
             known_enemy_position: vec2d::new(),
+            known_enemy_time: 0.0,
+            last_report_time: None,
             has_discovered_enemy: false,
-            health: 100.0,
+            is_in_contact: false,
+            soldiers: DEFAULT_SOLDIERS,
+            max_soldiers: DEFAULT_SOLDIERS,
+            pending_casualties: 0.0,
+            stamina: MAX_STAMINA,
+            morale: MAX_MORALE,
+            surprised_time: 0.0,
             status: CharacterStatus::Idle
         }
     }
 
     pub fn update(&mut self, dt: &f64){
+        if self.is_dead() {
+            return;
+        }
+
         self.position.x += self.speed.x * dt;
         self.position.y += self.speed.y * dt;
 
         match self.status {
-            CharacterStatus::Idle => self.health = num::clamp(self.health + dt, 0.0, 100.0),
-            CharacterStatus::Moving | CharacterStatus::Fighting => self.health = num::clamp(self.health - dt, 0.0, 100.0),
+            CharacterStatus::Idle => self.stamina = num::clamp(self.stamina + dt, 0.0, MAX_STAMINA),
+            CharacterStatus::Moving | CharacterStatus::Fighting | CharacterStatus::Routing => self.stamina = num::clamp(self.stamina - dt, 0.0, MAX_STAMINA),
+            CharacterStatus::Dead => ()
+        }
+
+        if self.status != CharacterStatus::Fighting {
+            self.raise_morale(dt * MORALE_RECOVERY);
+        }
+
+        self.surprised_time = (self.surprised_time - dt).max(0.0);
+
+        if self.status == CharacterStatus::Routing && self.morale >= RALLY_MORALE {
+            self.stop();
+            self.status = CharacterStatus::Idle;
         }
 
-        if collision::are_positions_colliding(&self.position, &self.known_enemy_position, collision::CollisionType::View){
+        // intel is only dropped from close enough to have seen the enemy even in cover
+        if collision::are_positions_in_range(&self.position, &self.known_enemy_position, self.get_view_radius() * CONCEALED_VIEW_FACTOR){
             self.has_discovered_enemy = false;
         }
 
         if self.has_reached_target(){
-            self.rest();
+            if self.status == CharacterStatus::Routing {
+                self.stop();
+            } else {
+                self.rest();
+            }
         }
     }
 
+    // speed is left to the caller since scouts, couriers and troops move at different paces
+    pub fn configure(&mut self, config: &config::GameConfig) {
+        self.view_radius = config.view_radius;
+        self.touch_radius = config.touch_radius;
+    }
+
     pub fn has_reached_target(&mut self) -> bool{
-        collision::are_positions_colliding(&self.position, &self.target_position, collision::CollisionType::Touch)
+        self.is_touching(&self.target_position)
+    }
+
+    pub fn is_touching(&self, position: &vec2d::Vec2D) -> bool {
+        collision::are_positions_in_range(&self.position, position, self.touch_radius)
     }
 
     pub fn rest(&mut self){
+        if !self.is_commandable() {
+            return;
+        }
         self.status = CharacterStatus::Idle;
+        self.stop();
+    }
+
+    fn stop(&mut self){
         self.speed.x = 0.0;
         self.speed.y = 0.0;
     }
 
     pub fn set_target(&mut self, target_position: &vec2d::Vec2D){
+        if !self.is_commandable() {
+            return;
+        }
         self.status = CharacterStatus::Moving;
+        self.move_towards(target_position, self.max_speed);
+    }
+
+    fn move_towards(&mut self, target_position: &vec2d::Vec2D, speed: f64){
         self.target_position.x = target_position.x;
         self.target_position.y = target_position.y;
 
         let length = ((self.target_position.x - self.position.x).powi(2) + (self.target_position.y - self.position.y).powi(2)).sqrt();
-        self.speed.x = (self.target_position.x - self.position.x) / length * self.max_speed;
-        self.speed.y = (self.target_position.y - self.position.y) / length * self.max_speed;
+        if length == 0.0 {
+            self.stop();
+            return;
+        }
+        self.heading.x = (self.target_position.x - self.position.x) / length;
+        self.heading.y = (self.target_position.y - self.position.y) / length;
+        self.speed.x = self.heading.x * speed;
+        self.speed.y = self.heading.y * speed;
+    }
+
+    // flee straight away from the threat, faster than a normal march
+    pub fn rout(&mut self, threat_position: &vec2d::Vec2D){
+        if !self.is_commandable() {
+            return;
+        }
+        let mut away = vec2d::Vec2D{ x: self.position.x - threat_position.x, y: self.position.y - threat_position.y };
+        let length = (away.x.powi(2) + away.y.powi(2)).sqrt();
+        if length == 0.0 {
+            away = vec2d::Vec2D{ x: -self.heading.x, y: -self.heading.y };
+        } else {
+            away.x /= length;
+            away.y /= length;
+        }
+        self.status = CharacterStatus::Routing;
+        let flee_position = vec2d::Vec2D{ x: self.position.x + away.x * ROUT_DISTANCE, y: self.position.y + away.y * ROUT_DISTANCE };
+        self.move_towards(&flee_position, self.max_speed * ROUT_SPEED_FACTOR);
     }
 
     pub fn get_position(&self) -> &vec2d::Vec2D {
@@ -100,8 +214,13 @@ impl Character {
         &self.known_enemy_position
     }
 
-    pub fn discover_enemy(&mut self, enemy_position: &vec2d::Vec2D){
+    pub fn get_known_enemy_time(&self) -> f64 {
+        self.known_enemy_time
+    }
+
+    pub fn discover_enemy(&mut self, enemy_position: &vec2d::Vec2D, time: f64){
         self.known_enemy_position = *enemy_position;
+        self.known_enemy_time = time;
         self.has_discovered_enemy = true;
     }
 
@@ -109,12 +228,124 @@ impl Character {
         self.has_discovered_enemy
     }
 
-    pub fn hurt(&mut self, health_loss: f64) {
-        self.health -= health_loss;
+    pub fn has_newer_intel_than(&self, other: &Character) -> bool {
+        self.has_discovered_enemy && (!other.has_discovered_enemy || self.known_enemy_time > other.known_enemy_time)
+    }
+
+    pub fn get_last_report_time(&self) -> Option<f64> {
+        self.last_report_time
+    }
+
+    pub fn set_last_report_time(&mut self, time: f64) {
+        self.last_report_time = Some(time);
+    }
+
+    pub fn is_in_contact(&self) -> bool {
+        self.is_in_contact
+    }
+
+    pub fn set_in_contact(&mut self, in_contact: bool) {
+        self.is_in_contact = in_contact;
+    }
+
+    // damage accumulates until it amounts to whole soldiers lost
+    pub fn hurt(&mut self, casualties: f64) {
+        if self.is_dead() {
+            return;
+        }
+        self.lower_morale(casualties / self.max_soldiers as f64 * CASUALTY_MORALE_FACTOR);
+        self.pending_casualties += casualties;
+        let soldiers_lost = self.pending_casualties.floor();
+        self.pending_casualties -= soldiers_lost;
+        self.soldiers = self.soldiers.saturating_sub(soldiers_lost as u32);
+
+        if self.soldiers == 0 {
+            self.stop();
+            self.status = CharacterStatus::Dead;
+        }
+    }
+
+    pub fn get_soldiers(&self) -> u32 {
+        self.soldiers
+    }
+
+    pub fn get_max_soldiers(&self) -> u32 {
+        self.max_soldiers
+    }
+
+    pub fn set_soldiers(&mut self, soldiers: u32) {
+        self.soldiers = soldiers;
+        self.max_soldiers = soldiers;
+    }
+
+    pub fn get_strength(&self) -> f64 {
+        self.soldiers as f64 / self.max_soldiers as f64
+    }
+
+    pub fn get_combat_power(&self) -> f64 {
+        if self.is_routing() {
+            return 0.0;
+        }
+        self.soldiers as f64
+    }
+
+    pub fn get_morale(&self) -> &f64 {
+        &self.morale
+    }
+
+    pub fn raise_morale(&mut self, amount: f64) {
+        self.morale = num::clamp(self.morale + amount, 0.0, MAX_MORALE);
+    }
+
+    pub fn lower_morale(&mut self, amount: f64) {
+        self.morale = num::clamp(self.morale - amount, 0.0, MAX_MORALE);
     }
 
-    pub fn get_health(&self) -> &f64{
-        &self.health
+    // caught unaware: the unit is shaken and fights badly for a while
+    pub fn surprise(&mut self, morale_shock: f64) {
+        self.surprised_time = SURPRISE_DURATION;
+        self.lower_morale(morale_shock);
+    }
+
+    pub fn is_surprised(&self) -> bool {
+        self.surprised_time > 0.0
+    }
+
+    // a stationary unit keeps to cover and is only seen from closer
+    pub fn get_concealment(&self) -> f64 {
+        if self.status == CharacterStatus::Idle { CONCEALED_VIEW_FACTOR } else { 1.0 }
+    }
+
+    // an attacker behind the direction of march catches the unit in column
+    pub fn is_attacked_from_behind(&self, attacker_position: &vec2d::Vec2D) -> bool {
+        let to_attacker_x = attacker_position.x - self.position.x;
+        let to_attacker_y = attacker_position.y - self.position.y;
+        self.heading.x * to_attacker_x + self.heading.y * to_attacker_y < 0.0
+    }
+
+    // a depleted unit has fewer eyes out, down to half the full view radius
+    pub fn get_view_radius(&self) -> f64 {
+        self.view_radius * (MIN_VIEW_FACTOR + (1.0 - MIN_VIEW_FACTOR) * self.get_strength())
+    }
+
+    pub fn get_stamina(&self) -> &f64 {
+        &self.stamina
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.status == CharacterStatus::Dead
+    }
+
+    pub fn is_routing(&self) -> bool {
+        self.status == CharacterStatus::Routing
+    }
+
+    pub fn has_broken(&self) -> bool {
+        self.is_commandable() && self.morale < ROUT_MORALE
+    }
+
+    pub fn is_commandable(&self) -> bool {
+        !self.is_dead() && !self.is_routing()
     }
 
     pub fn get_status(&self) -> &CharacterStatus {
@@ -122,6 +353,9 @@ impl Character {
     }
 
     pub fn fight(&mut self) {
+        if !self.is_commandable() {
+            return;
+        }
         self.rest();
         self.status = CharacterStatus::Fighting;
     }
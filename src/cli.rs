@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+use clap::Parser;
+use clap::builder::PossibleValuesParser;
+use war_fog_experiment::args::GameArgs;
+use war_fog_experiment::strategy;
+
+const DEFAULT_MAP_PATH: &str = "./assets/map_2.jpg";
+const DEFAULT_HEADLESS_DURATION: f64 = 600.0;
+const DEFAULT_SCOUT_INTERVAL: f64 = 30.0;
+
+/// A real-time tactics experiment about commanding troops through the fog of war
+#[derive(Parser)]
+#[command(version)]
+pub struct Cli {
+    /// Window width in pixels, overrides the config file
+    #[arg(long)]
+    pub width: Option<u32>,
+
+    /// Window height in pixels, overrides the config file
+    #[arg(long)]
+    pub height: Option<u32>,
+
+    /// Open the window in fullscreen
+    #[arg(long)]
+    pub fullscreen: bool,
+
+    /// Map image, centred on the world origin
+    #[arg(long, value_name = "FILE", default_value = DEFAULT_MAP_PATH)]
+    pub map: PathBuf,
+
+    /// Seed for the enemy placement and behaviour, random if not given
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub game: GameArgs,
+
+    /// Record the game to a replay file when it ends
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Play back a recorded replay instead of a new game
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// Let a bot command the player's side and watch it play against the enemy
+    #[arg(long, value_name = "STRATEGY", conflicts_with = "replay", value_parser = PossibleValuesParser::new(strategy::STRATEGY_KINDS))]
+    pub bot: Option<String>,
+
+    /// Seconds between scout missions for the scout-every bot
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_SCOUT_INTERVAL)]
+    pub scout_interval: f64,
+
+    /// Run the simulation without a window and print the result
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Longest game time in seconds a headless run goes on for
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_HEADLESS_DURATION)]
+    pub duration: f64
+}
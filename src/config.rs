@@ -0,0 +1,142 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::character;
+use crate::collision;
+use crate::enemy;
+use crate::messaging;
+use crate::scout;
+
+pub const DEFAULT_CONFIG_PATH: &str = "config.json";
+const CAMERA_MOVE_SPEED: f64 = 200.0;
+const WINDOW_WIDTH: u32 = 1280;
+const WINDOW_HEIGHT: u32 = 1080;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Format(serde_json::Error),
+    UnknownSetting(String),
+    InvalidOverride(String),
+    InvalidValue { name: &'static str, reason: &'static str }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "could not read config file: {error}"),
+            ConfigError::Format(error) => write!(f, "invalid config: {error}"),
+            ConfigError::UnknownSetting(name) => write!(f, "unknown setting {name}"),
+            ConfigError::InvalidOverride(setting) => write!(f, "expected name=value, got {setting}"),
+            ConfigError::InvalidValue { name, reason } => write!(f, "invalid {name}: {reason}")
+        }
+    }
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(error: std::io::Error) -> Self {
+        ConfigError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(error: serde_json::Error) -> Self {
+        ConfigError::Format(error)
+    }
+}
+
+// settings missing from a config file keep their default value
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub character_speed: f64,
+    pub scout_speed: f64,
+    pub courier_speed: f64,
+    pub touch_radius: f64,
+    pub view_radius: f64,
+    pub fight_radius: f64,
+    pub enemy_rest_stamina: f64,
+    pub enemy_chase_stamina: f64,
+    pub camera_move_speed: f64,
+    pub window_width: u32,
+    pub window_height: u32
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            character_speed: character::CHARACTER_SPEED,
+            scout_speed: scout::SCOUT_SPEED,
+            courier_speed: messaging::COURIER_SPEED,
+            touch_radius: collision::TOUCH_RADIUS,
+            view_radius: collision::VIEW_RADIUS,
+            fight_radius: collision::FIGHT_RADIUS,
+            enemy_rest_stamina: enemy::REST_STAMINA,
+            enemy_chase_stamina: enemy::CHASE_STAMINA,
+            camera_move_speed: CAMERA_MOVE_SPEED,
+            window_width: WINDOW_WIDTH,
+            window_height: WINDOW_HEIGHT
+        }
+    }
+}
+
+impl GameConfig {
+
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let contents = fs::read_to_string(path)?;
+        let config: GameConfig = serde_json::from_str(&contents)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    // an explicit path must exist, otherwise config.json is used when present; overrides apply on top
+    pub fn load_with_overrides(path: Option<&Path>, overrides: &[String]) -> Result<Self, ConfigError> {
+        let mut config = match path {
+            Some(path) => GameConfig::load(path)?,
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => GameConfig::load(Path::new(DEFAULT_CONFIG_PATH))?,
+            None => GameConfig::default()
+        };
+        for setting in overrides.iter() {
+            config.apply_override(setting)?;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    // settings that would leave units unable to move, see or fight
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let positive_settings = [
+            ("character_speed", self.character_speed),
+            ("scout_speed", self.scout_speed),
+            ("courier_speed", self.courier_speed),
+            ("touch_radius", self.touch_radius),
+            ("view_radius", self.view_radius),
+            ("fight_radius", self.fight_radius),
+            ("camera_move_speed", self.camera_move_speed)
+        ];
+        for (name, value) in positive_settings {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(ConfigError::InvalidValue { name, reason: "must be a number greater than 0" });
+            }
+        }
+        if self.window_width == 0 || self.window_height == 0 {
+            return Err(ConfigError::InvalidValue { name: "window size", reason: "must be greater than 0" });
+        }
+        // the enemy has to stop to rest before it is too tired to chase
+        if self.enemy_chase_stamina > self.enemy_rest_stamina {
+            return Err(ConfigError::InvalidValue { name: "enemy_chase_stamina", reason: "must not be above enemy_rest_stamina" });
+        }
+        Ok(())
+    }
+
+    // "name=value" as given on the command line, the value is read as JSON
+    pub fn apply_override(&mut self, setting: &str) -> Result<(), ConfigError> {
+        let (name, value) = setting.split_once('=').ok_or_else(|| ConfigError::InvalidOverride(setting.to_string()))?;
+        let mut settings = serde_json::to_value(&*self)?;
+        let field = settings.get_mut(name.trim()).ok_or_else(|| ConfigError::UnknownSetting(name.trim().to_string()))?;
+        *field = serde_json::from_str(value.trim())?;
+        *self = serde_json::from_value(settings)?;
+        Ok(())
+    }
+}
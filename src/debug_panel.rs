@@ -0,0 +1,108 @@
+use std::time::Instant;
+use piston_window::*;
+use war_fog_experiment::snapshot::{Side, UnitKind, UnitView, WorldSnapshot};
+use war_fog_experiment::vec2d;
+use crate::message_log;
+use crate::time_control::TimeControl;
+
+const PANEL_LEFT: f64 = 20.0;
+const PANEL_TOP: f64 = 240.0;
+const PANEL_WIDTH: f64 = 720.0;
+const PANEL_PADDING: f64 = 8.0;
+const LINE_HEIGHT: f64 = 18.0;
+const FONT_SIZE: u32 = 14;
+const PANEL_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.7];
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const FPS_INTERVAL: f64 = 1.0;
+
+pub struct DebugPanel {
+    is_visible: bool,
+    frame_count: u32,
+    fps: f64,
+    fps_interval_start: Instant
+}
+
+impl DebugPanel {
+
+    pub fn new() -> Self {
+        Self {
+            is_visible: false,
+            frame_count: 0,
+            fps: 0.0,
+            fps_interval_start: Instant::now()
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_visible = !self.is_visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    pub fn record_frame(&mut self) {
+        self.frame_count += 1;
+        let elapsed = self.fps_interval_start.elapsed().as_secs_f64();
+        if elapsed >= FPS_INTERVAL {
+            self.fps = self.frame_count as f64 / elapsed;
+            self.frame_count = 0;
+            self.fps_interval_start = Instant::now();
+        }
+    }
+
+    pub fn draw(&self, snapshot: &WorldSnapshot, time_control: &TimeControl, font: &mut Glyphs, c: &Context, gl: &mut G2d) {
+        if !self.is_visible {
+            return;
+        }
+
+        let mut lines = vec![
+            format!("Tick {}   Time {}   FPS {:.0}", snapshot.tick, message_log::format_game_time(snapshot.time), self.fps),
+            format!("Speed x{}{}   [Space] pause  [N] step  [-/+] speed", time_control.get_time_scale(), if time_control.is_paused() { " (paused)" } else { "" }),
+        ];
+
+        if let Some(enemy_intel) = snapshot.enemy_intel {
+            lines.push(format!("Commander's intel : enemy at {} ({:.0}s old)", format_position(&enemy_intel.position), snapshot.time - enemy_intel.observed_at));
+        }
+        if let Some(route) = snapshot.scout_route {
+            let target = route.target_position.map(|target| format_position(&target)).unwrap_or("-".to_string());
+            lines.push(format!("Scout route : target {target}, return {}", format_position(&route.return_position)));
+        }
+
+        lines.extend(snapshot.units.iter().map(|unit| describe_unit(unit, snapshot.time)));
+
+        let panel_height = PANEL_PADDING * 2.0 + LINE_HEIGHT * lines.len() as f64;
+        rectangle(PANEL_COLOR, [PANEL_LEFT, PANEL_TOP, PANEL_WIDTH, panel_height], c.transform, gl);
+        for (index, line) in lines.iter().enumerate() {
+            let line_transform = c.transform.trans(PANEL_LEFT + PANEL_PADDING, PANEL_TOP + PANEL_PADDING + LINE_HEIGHT * (index + 1) as f64 - 4.0);
+            text(TEXT_COLOR, FONT_SIZE, line, font, line_transform, gl).unwrap();
+        }
+    }
+}
+
+fn describe_unit(unit: &UnitView, time: f64) -> String {
+    let name = match (unit.side, unit.kind, unit.id) {
+        (Side::Enemy, _, _) => "Enemy".to_string(),
+        (Side::Friendly, UnitKind::Courier, _) => "Courier".to_string(),
+        (Side::Friendly, _, Some(id)) => message_log::describe_unit(id),
+        (Side::Friendly, _, None) => "Unknown".to_string()
+    };
+    let status = match unit.scout_status {
+        Some(scout_status) => format!("{:?}/{:?}", unit.status, scout_status),
+        None => format!("{:?}", unit.status)
+    };
+    let target = unit.target_position.map(|target| format_position(&target)).unwrap_or("-".to_string());
+    let morale = unit.morale.map(|morale| format!("{morale:.0}")).unwrap_or("?".to_string());
+    let stamina = unit.stamina.map(|stamina| format!("{stamina:.0}")).unwrap_or("?".to_string());
+    let intel = unit.known_enemy_time.map(|observed_at| format!("{:.0}s", time - observed_at)).unwrap_or("-".to_string());
+    format!(
+        "{name} : {status} at {} -> {target}, men {}/{}, morale {morale}, stamina {stamina}, intel age {intel}{}{}",
+        format_position(&unit.position), unit.soldiers, unit.max_soldiers,
+        if unit.is_surprised { ", surprised" } else { "" },
+        if unit.is_carrying_report { ", carrying report" } else { "" }
+    )
+}
+
+fn format_position(position: &vec2d::Vec2D) -> String {
+    format!("({:.0}, {:.0})", position.x, position.y)
+}
@@ -1,6 +1,9 @@
 use crate::character::{Character, CharacterStatus};
 use crate::vec2d;
 
+pub const REST_STAMINA: f64 = 75.0;
+pub const CHASE_STAMINA: f64 = 50.0;
+
 pub trait Enemy {
     fn new() -> Self;
     fn update(&mut self, dt: &f64);
@@ -13,7 +16,7 @@ impl Enemy for Character {
     }
 
     fn update(&mut self, _dt: &f64){
-        if *self.get_status() == CharacterStatus::Idle && *self.get_health() > 90.0 {
+        if *self.get_status() == CharacterStatus::Idle && *self.get_stamina() > 90.0 {
             self.set_target(&vec2d::Vec2D{ x: (rand::random::<f64>() -0.5) * 1000.0, y: (rand::random::<f64>() - 0.5) * 1000.0});
         }
     }
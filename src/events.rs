@@ -0,0 +1,21 @@
+use crate::game::UnitId;
+use crate::snapshot::{Outcome, Side};
+use crate::vec2d;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GameEvent {
+    EnemySighted { position: vec2d::Vec2D },
+    ScoutReturned { has_report: bool },
+    ReportDelivered { enemy_position: vec2d::Vec2D, observed_at: f64 },
+    CourierDispatched { sender: UnitId, recipient: UnitId },
+    CourierIntercepted { sender: UnitId },
+    CourierLost { sender: UnitId },
+    OrderDelivered { unit: UnitId, latency: f64 },
+    OrderIgnored { unit: UnitId },
+    FightStarted { unit: UnitId },
+    Ambushed { side: Side, unit: Option<UnitId> },
+    UnitRouted { side: Side, unit: Option<UnitId> },
+    UnitRallied { side: Side, unit: Option<UnitId> },
+    UnitDied { side: Side, unit: Option<UnitId> },
+    GameOver { outcome: Outcome }
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use crate::vec2d;
+
+pub const CELL_SIZE: f64 = 50.0;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExploredArea {
+    cells: HashSet<(i32, i32)>
+}
+
+impl Default for ExploredArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExploredArea {
+
+    pub fn new() -> Self {
+        Self {
+            cells: HashSet::new()
+        }
+    }
+
+    // marks every cell whose centre lies within the same square range the collision checks use
+    pub fn reveal(&mut self, position: &vec2d::Vec2D, radius: f64) {
+        let (min_x, min_y) = get_cell(&vec2d::Vec2D{ x: position.x - radius, y: position.y - radius });
+        let (max_x, max_y) = get_cell(&vec2d::Vec2D{ x: position.x + radius, y: position.y + radius });
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                let centre_x = (x as f64 + 0.5) * CELL_SIZE;
+                let centre_y = (y as f64 + 0.5) * CELL_SIZE;
+                if (centre_x - position.x).abs() < radius && (centre_y - position.y).abs() < radius {
+                    self.cells.insert((x, y));
+                }
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: &ExploredArea) {
+        self.cells.extend(other.cells.iter().copied());
+    }
+
+    pub fn is_explored(&self, position: &vec2d::Vec2D) -> bool {
+        self.cells.contains(&get_cell(position))
+    }
+}
+
+fn get_cell(position: &vec2d::Vec2D) -> (i32, i32) {
+    ((position.x / CELL_SIZE).floor() as i32, (position.y / CELL_SIZE).floor() as i32)
+}
\ No newline at end of file
@@ -1,3 +1,7 @@
+use std::rc::Rc;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
 use crate::character;
 use crate::enemy;
 use crate::scout;
@@ -5,152 +9,714 @@ use crate::scout::ScoutStatus;
 use crate::vec2d;
 use crate::collision;
 use crate::character::CharacterStatus;
+use crate::messaging;
+use crate::messaging::{CourierStatus, MessageContent};
+use crate::snapshot;
+use crate::snapshot::{Outcome, Side, UnitKind};
+use crate::events::GameEvent;
+use crate::exploration;
+use crate::config;
+use crate::scenario;
+use crate::mission::{MissionEnd, Objective};
+use crate::after_action;
+
+const CASUALTY_RATE: f64 = 0.02;
+const FLANKED_MORALE_LOSS: f64 = 5.0;
+const ALLY_MORALE_SUPPORT: f64 = 0.5;
+const AMBUSH_MULTIPLIER: f64 = 2.0;
+const SURPRISE_MORALE_SHOCK: f64 = 30.0;
+const REPORT_INTERVAL: f64 = 10.0;
+const COURIER_LOSS_RATE: f64 = 0.002;
+
+pub const COMMANDER: usize = 0;
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum UnitId {
+    Unit(usize),
+    Scout
+}
 
+#[derive(Serialize, Deserialize)]
 pub struct Game {
-    player: character::Character,
+    config: config::GameConfig,
+    scenario: String,
+    friendly_units: Vec<character::Character>,
     scout: scout::Scout,
-    enemy: character::Character
+    enemy: character::Character,
+    enemy_camp: vec2d::Vec2D,
+    objective: Objective,
+    mission_end: Option<MissionEnd>,
+    couriers: Vec<messaging::Courier>,
+    order_latencies: Vec<f64>,
+    statistics: after_action::Statistics,
+    explored_area: Rc<exploration::ExploredArea>,
+    time: f64,
+    tick: u64,
+    rng: Pcg64,
+    #[serde(skip)]
+    events: Vec<GameEvent>
 }
 
 
 
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Game {
 
     pub fn new() -> Game{
+        Game::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Game{
+        Game::with_config(config::GameConfig::default(), seed)
+    }
+
+    pub fn with_config(config: config::GameConfig, seed: u64) -> Game{
+        Game::with_scenario(scenario::get_default_scenario(), config, seed)
+    }
+
+    pub fn with_scenario(scenario: &scenario::Scenario, config: config::GameConfig, seed: u64) -> Game{
+        let friendly_units = scenario.get_friendly_positions().map(|(position, soldiers)| {
+            let mut unit = character::Character::new();
+            unit.configure(&config);
+            unit.set_max_speed(config.character_speed);
+            unit.set_position(&position);
+            unit.set_soldiers(soldiers);
+            unit
+        }).collect();
+
+        let mut scout = scout::Scout::new();
+        scout.configure(&config);
+        let mut enemy: character::Character = enemy::Enemy::new();
+        enemy.configure(&config);
+        enemy.set_max_speed(config.character_speed);
+        enemy.set_soldiers(scenario.enemy_soldiers);
+
         let mut game = Game {
-            player: character::Character::new(),
-            scout: scout::Scout::new(),
-            enemy: enemy::Enemy::new()
+            config,
+            scenario: scenario.name.to_string(),
+            friendly_units,
+            scout,
+            enemy,
+            enemy_camp: vec2d::new(),
+            objective: scenario.objective,
+            mission_end: None,
+            couriers: Vec::new(),
+            order_latencies: Vec::new(),
+            statistics: after_action::Statistics::default(),
+            explored_area: Rc::new(exploration::ExploredArea::new()),
+            time: 0.0,
+            tick: 0,
+            rng: Pcg64::seed_from_u64(seed),
+            events: Vec::new()
         };
-    
-        game.enemy.set_position(&vec2d::Vec2D{ x: (rand::random::<f64>() -0.5) * 1000.0, y: (rand::random::<f64>() - 0.5) * 1000.0});
-    
-        return game   
+
+        let enemy_position = vec2d::Vec2D{ x: (game.rng.gen::<f64>() -0.5) * 2.0 * scenario.enemy_spawn_range, y: (game.rng.gen::<f64>() - 0.5) * 2.0 * scenario.enemy_spawn_range};
+        game.enemy.set_position(&enemy_position);
+        game.enemy_camp = enemy_position;
+        if let Objective::DefendPosition { position, .. } = game.objective {
+            game.enemy.set_target(&position);
+        }
+
+        game
     }
 
-    pub fn update(&mut self, dt: &f64){
-        self.player.update(dt);
+    pub fn update(&mut self, dt: &f64) -> Vec<GameEvent>{
+        // awareness as it stood before anyone moved this tick
+        let were_units_aware: Vec<bool> = self.friendly_units.iter().map(|unit| self.enemy.is_visible() || is_aware_of(unit, &self.enemy)).collect();
+        let was_enemy_aware: Vec<bool> = self.friendly_units.iter().map(|unit| unit.is_visible()).collect();
+        let was_enemy_visible = self.enemy.is_visible();
+        let previous_statuses: Vec<CharacterStatus> = self.friendly_units.iter().map(|unit| *unit.get_status()).collect();
+        let previous_enemy_status = *self.enemy.get_status();
+        let previous_positions: Vec<vec2d::Vec2D> = self.friendly_units.iter().map(|unit| *unit.get_position()).collect();
+        let was_over = self.is_over();
+
+        self.time += dt;
+        self.tick += 1;
+
+        for unit in self.friendly_units.iter_mut() {
+            unit.update(dt);
+        }
         self.scout.update(dt);
         self.enemy.update(dt);
 
 
-        let enemy_health = *self.enemy.get_health();
-        if enemy_health < 75.0 {
+        let enemy_stamina = *self.enemy.get_stamina();
+        if enemy_stamina < self.config.enemy_rest_stamina {
             self.enemy.rest();
         }
 
-        // player/scout interaction
-        if collision::are_positions_colliding(self.player.get_position(),self.scout.get_position(),collision::CollisionType::View){
-            match *self.scout.get_status() {
-                ScoutStatus::GoingToPlayer => {
-                    self.scout.set_target(self.player.get_position());
-                },
-                _ => ()
-            }
-            
+        // commander/scout interaction
+        let commander_position = *self.friendly_units[COMMANDER].get_position();
+        let commander_view_radius = self.friendly_units[COMMANDER].get_view_radius();
+        if collision::are_positions_in_range(&commander_position, self.scout.get_position(), commander_view_radius) && *self.scout.get_status() == ScoutStatus::GoingToPlayer {
+            self.scout.set_target(&commander_position);
         }
 
-        if collision::are_positions_colliding(self.player.get_position(),self.scout.get_position(),collision::CollisionType::Touch){
-            match *self.scout.get_status() {
-                ScoutStatus::GoingToPlayer => {
-                    self.scout.set_idle();
-
-                    if self.scout.has_enemy_position_to_deliver(){
-                        let enemy_position = self.scout.deliver_enemy_position();
-                        self.player.discover_enemy(enemy_position);
-                    }
-                },
-                _ => ()
+        if collision::are_positions_in_range(&commander_position, self.scout.get_position(), self.config.touch_radius) && *self.scout.get_status() == ScoutStatus::GoingToPlayer {
+            self.scout.set_idle();
+            Rc::make_mut(&mut self.explored_area).merge(&self.scout.take_explored_area());
+
+            let has_report = self.scout.has_enemy_position_to_deliver();
+            self.statistics.scout_missions_returned += 1;
+            self.events.push(GameEvent::ScoutReturned { has_report });
+            if has_report {
+                let enemy_time = self.scout.get_known_enemy_time();
+                let enemy_position = *self.scout.deliver_enemy_position();
+                self.friendly_units[COMMANDER].discover_enemy(&enemy_position, enemy_time);
+                self.events.push(GameEvent::ReportDelivered { enemy_position, observed_at: enemy_time });
             }
-            
         }
 
-        let is_scout_visible = collision::are_positions_colliding(self.player.get_position(), self.scout.get_position(), collision::CollisionType::View);
+        // shared fog: anything one friendly unit sees, the player sees
+        let is_scout_visible = self.friendly_units.iter()
+            .any(|unit| !unit.is_dead() && collision::are_positions_in_range(unit.get_position(), self.scout.get_position(), unit.get_view_radius()));
         self.scout.set_visible(is_scout_visible);
 
-        
-
         // scout/enemy interaction
 
-        if collision::are_positions_colliding(self.scout.get_position(), self.enemy.get_position(), collision::CollisionType::View){
-            self.scout.discover_enemy(self.enemy.get_position());
+        if collision::are_positions_in_range(self.scout.get_position(), self.enemy.get_position(), self.scout.get_view_radius() * self.enemy.get_concealment()){
+            self.scout.discover_enemy(self.enemy.get_position(), self.time);
         }
 
-        // player/enemy interaction
+        // friendly units/enemy interaction
 
-        let is_enemy_visible = collision::are_positions_colliding(self.player.get_position(), self.enemy.get_position(), collision::CollisionType::View);
+        let mut is_enemy_visible = false;
+        for unit in self.friendly_units.iter_mut().filter(|unit| !unit.is_dead()) {
+            if is_in_sight(unit, &self.enemy) {
+                unit.discover_enemy(self.enemy.get_position(), self.time);
+                is_enemy_visible = true;
+            }
+            unit.set_visible(is_in_sight(&self.enemy, unit));
+        }
         self.enemy.set_visible(is_enemy_visible);
-        if is_enemy_visible {
-            self.player.discover_enemy(self.enemy.get_position());
+        if is_enemy_visible && !was_enemy_visible {
+            self.events.push(GameEvent::EnemySighted { position: *self.enemy.get_position() });
+        }
+
+        self.reveal_explored_area();
+        self.share_intel();
+        self.dispatch_reports();
+        self.update_couriers(dt);
+        self.support_morale(dt, is_scout_visible);
+
+        if enemy_stamina > self.config.enemy_chase_stamina {
+            if let Some(target) = self.get_nearest_friendly_unit(self.enemy.get_position(), |unit| unit.is_visible()) {
+                let target_position = *self.friendly_units[target].get_position();
+                self.enemy.set_target(&target_position);
+            }
+        }
+
+        self.resolve_combat(dt, &were_units_aware, &was_enemy_aware);
+
+        for unit in self.friendly_units.iter_mut() {
+            if unit.has_broken() {
+                unit.rout(self.enemy.get_position());
+            }
+        }
+        if self.enemy.has_broken() {
+            if let Some(nearest) = self.get_nearest_friendly_unit(self.enemy.get_position(), |_| true) {
+                let threat_position = *self.friendly_units[nearest].get_position();
+                self.enemy.rout(&threat_position);
+            }
+        }
+
+        for (index, previous_status) in previous_statuses.iter().enumerate() {
+            let status = *self.friendly_units[index].get_status();
+            push_status_events(&mut self.events, Side::Friendly, Some(UnitId::Unit(index)), previous_status, &status);
+        }
+        push_status_events(&mut self.events, Side::Enemy, None, &previous_enemy_status, self.enemy.get_status());
+
+        for (unit, previous_position) in self.friendly_units.iter().zip(previous_positions.iter()) {
+            let position = unit.get_position();
+            self.statistics.distance_travelled += ((position.x - previous_position.x).powi(2) + (position.y - previous_position.y).powi(2)).sqrt();
+        }
+
+        self.check_mission();
+        if !was_over {
+            if let Some(outcome) = self.get_outcome() {
+                self.events.push(GameEvent::GameOver { outcome });
+            }
+        }
+
+        std::mem::take(&mut self.events)
+    }
+
+    fn resolve_combat(&mut self, dt: &f64, were_units_aware: &[bool], was_enemy_aware: &[bool]) {
+        let enemy_position = *self.enemy.get_position();
+        let mut engaged_units = Vec::new();
+
+        for (index, unit) in self.friendly_units.iter_mut().enumerate() {
+            let is_in_contact = !unit.is_dead() && collision::are_positions_in_range(unit.get_position(), &enemy_position, self.config.fight_radius);
+            if is_in_contact && !unit.is_in_contact() {
+                self.events.push(GameEvent::FightStarted { unit: UnitId::Unit(index) });
+                // contact from the fog: the defender had neither sight nor intel of its attacker
+                if !were_units_aware[index] {
+                    unit.surprise(SURPRISE_MORALE_SHOCK);
+                    self.events.push(GameEvent::Ambushed { side: Side::Friendly, unit: Some(UnitId::Unit(index)) });
+                }
+                if !was_enemy_aware[index] {
+                    self.enemy.surprise(SURPRISE_MORALE_SHOCK);
+                    self.events.push(GameEvent::Ambushed { side: Side::Enemy, unit: None });
+                }
+            }
+            unit.set_in_contact(is_in_contact);
+
+            if is_in_contact {
+                engaged_units.push(index);
+            }
+        }
+
+        if engaged_units.is_empty() {
+            return;
+        }
+
+        self.enemy.fight();
+
+        // the enemy splits its effort between every unit it is engaged with
+        let enemy_power = self.enemy.get_combat_power() / engaged_units.len() as f64;
+        let mut enemy_casualties = 0.0;
+        let mut is_enemy_flanked = engaged_units.len() > 1;
+
+        for index in engaged_units {
+            let unit = &mut self.friendly_units[index];
+            if *unit.get_status() == CharacterStatus::Idle{
+                unit.fight();
+            }
+
+            enemy_casualties += dt * CASUALTY_RATE * unit.get_combat_power() * get_ambush_multiplier(&self.enemy);
+            unit.hurt(dt * CASUALTY_RATE * enemy_power * get_ambush_multiplier(unit));
+
+            if unit.is_attacked_from_behind(&enemy_position) {
+                unit.lower_morale(dt * FLANKED_MORALE_LOSS);
+            }
+            if self.enemy.is_attacked_from_behind(unit.get_position()) {
+                is_enemy_flanked = true;
+            }
+        }
+
+        self.enemy.hurt(enemy_casualties);
+        if is_enemy_flanked {
+            self.enemy.lower_morale(dt * FLANKED_MORALE_LOSS);
+        }
+    }
+
+    fn check_mission(&mut self) {
+        if self.mission_end.is_some() {
+            return;
+        }
+        self.mission_end = if self.friendly_units[COMMANDER].is_dead() {
+            Some(MissionEnd::CommanderKilled)
+        } else if self.enemy.is_dead() {
+            Some(MissionEnd::EnemyDestroyed)
+        } else {
+            self.check_objective()
+        };
+    }
+
+    fn check_objective(&self) -> Option<MissionEnd> {
+        let commander = &self.friendly_units[COMMANDER];
+        match self.objective {
+            Objective::DestroyEnemy => None,
+            Objective::LocateEnemyCamp { time_limit } => {
+                // the report has to put the enemy at its camp, not wherever it chased someone to
+                if commander.has_discovered_enemy() && collision::are_positions_in_range(commander.get_known_enemy_position(), &self.enemy_camp, commander.get_view_radius()) {
+                    Some(MissionEnd::CampLocated)
+                } else if self.time >= time_limit {
+                    Some(MissionEnd::CampNotFound)
+                } else {
+                    None
+                }
+            },
+            Objective::ReachExtraction { position, radius } => {
+                if self.friendly_units.iter().any(|unit| !unit.is_dead() && unit.is_visible()) {
+                    Some(MissionEnd::Detected)
+                } else if collision::are_positions_in_range(commander.get_position(), &position, radius) {
+                    Some(MissionEnd::Extracted)
+                } else {
+                    None
+                }
+            },
+            Objective::EscortConvoy { convoy, destination, radius } => {
+                let convoy = self.friendly_units.get(convoy)?;
+                if convoy.is_dead() {
+                    Some(MissionEnd::ConvoyLost)
+                } else if collision::are_positions_in_range(convoy.get_position(), &destination, radius) {
+                    Some(MissionEnd::ConvoyArrived)
+                } else {
+                    None
+                }
+            },
+            Objective::DefendPosition { position, radius, duration } => {
+                let is_held = self.friendly_units.iter().any(|unit| !unit.is_dead() && collision::are_positions_in_range(unit.get_position(), &position, radius));
+                if !is_held && collision::are_positions_in_range(self.enemy.get_position(), &position, radius) {
+                    Some(MissionEnd::PositionLost)
+                } else if self.time >= duration {
+                    Some(MissionEnd::PositionHeld)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    // only copies the area when a snapshot still holds on to it
+    fn reveal_explored_area(&mut self) {
+        let explored_area = Rc::make_mut(&mut self.explored_area);
+        for unit in self.friendly_units.iter().filter(|unit| !unit.is_dead()) {
+            explored_area.reveal(unit.get_position(), unit.get_view_radius());
+        }
+    }
 
-            if enemy_health > 50.0 {
-                self.enemy.set_target(self.player.get_position());
+    // units within sight of each other pass on whatever they know that is fresher
+    fn share_intel(&mut self) {
+        for receiver in 0..self.friendly_units.len() {
+            for sender in 0..self.friendly_units.len() {
+                let (receiving_unit, sending_unit) = (&self.friendly_units[receiver], &self.friendly_units[sender]);
+                if receiver == sender || receiving_unit.is_dead() || sending_unit.is_dead() {
+                    continue;
+                }
+                if collision::are_positions_in_range(receiving_unit.get_position(), sending_unit.get_position(), receiving_unit.get_view_radius())
+                    && sending_unit.has_newer_intel_than(receiving_unit) {
+                    let enemy_position = *sending_unit.get_known_enemy_position();
+                    let enemy_time = sending_unit.get_known_enemy_time();
+                    self.friendly_units[receiver].discover_enemy(&enemy_position, enemy_time);
+                }
             }
         }
+    }
+
+    // detached units out of sight of the commander send what they know back by courier
+    fn dispatch_reports(&mut self) {
+        let commander = &self.friendly_units[COMMANDER];
+        let are_within_earshot: Vec<bool> = self.friendly_units.iter().map(|unit| is_within_earshot(commander, unit)).collect();
 
-        if collision::are_positions_colliding(self.player.get_position(), self.enemy.get_position(), collision::CollisionType::Fight){
-            if *self.player.get_status() == CharacterStatus::Idle{
-                self.player.fight();
+        for (index, unit) in self.friendly_units.iter_mut().enumerate() {
+            if index == COMMANDER || unit.is_dead() || !unit.has_discovered_enemy() {
+                continue;
+            }
+            // within earshot the commander already gets the intel through share_intel
+            if are_within_earshot[index] {
+                continue;
+            }
+            let is_report_due = match unit.get_last_report_time() {
+                Some(last_report_time) => unit.get_known_enemy_time() >= last_report_time + REPORT_INTERVAL,
+                None => true
+            };
+            if !is_report_due {
+                continue;
             }
-            self.enemy.fight();
 
-            self.player.hurt(dt * 2.0 * enemy_health / 100.0);
-            self.enemy.hurt(dt * 2.0 * self.player.get_health() / 100.0);
+            let message = messaging::Message {
+                content: MessageContent::EnemyReport { enemy_position: *unit.get_known_enemy_position(), observed_at: unit.get_known_enemy_time() },
+                sender: index,
+                recipient: COMMANDER,
+                sent_at: self.time
+            };
+            self.couriers.push(messaging::Courier::new(message, unit.get_position(), &self.config));
+            self.events.push(GameEvent::CourierDispatched { sender: UnitId::Unit(index), recipient: UnitId::Unit(COMMANDER) });
+            unit.set_last_report_time(unit.get_known_enemy_time());
         }
-        
     }
 
-    pub fn get_player_position(&self) -> &vec2d::Vec2D{
-        self.player.get_position()
+    fn update_couriers(&mut self, dt: &f64) {
+        let mut delivered_messages = Vec::new();
+
+        for courier in self.couriers.iter_mut() {
+            let recipient = &self.friendly_units[courier.get_message().recipient];
+            let sender = UnitId::Unit(courier.get_message().sender);
+            if recipient.is_dead() {
+                courier.set_status(CourierStatus::Lost);
+                self.events.push(GameEvent::CourierLost { sender });
+                continue;
+            }
+
+            courier.update(dt, recipient.get_position());
+
+            if courier.has_reached(recipient.get_position()) {
+                courier.set_status(CourierStatus::Delivered);
+                delivered_messages.push(*courier.get_message());
+            }
+            else if !self.enemy.is_dead() && collision::are_positions_in_range(courier.get_position(), self.enemy.get_position(), self.config.fight_radius) {
+                // a captured dispatch gives away where it was sent from
+                courier.set_status(CourierStatus::Intercepted);
+                let sender_position = *self.friendly_units[courier.get_message().sender].get_position();
+                self.enemy.set_target(&sender_position);
+                self.events.push(GameEvent::CourierIntercepted { sender });
+            }
+            else if self.rng.gen::<f64>() < dt * COURIER_LOSS_RATE {
+                courier.set_status(CourierStatus::Lost);
+                self.events.push(GameEvent::CourierLost { sender });
+            }
+
+            let is_visible = self.friendly_units.iter()
+                .any(|unit| !unit.is_dead() && collision::are_positions_in_range(unit.get_position(), courier.get_position(), unit.get_view_radius()));
+            courier.set_visible(is_visible);
+        }
+
+        self.couriers.retain(|courier| *courier.get_status() == CourierStatus::EnRoute);
+
+        for message in delivered_messages {
+            self.deliver_message(&message);
+        }
     }
 
-    pub fn get_player_target_position(&self) -> &vec2d::Vec2D{
-        self.player.get_target_position()
+    fn deliver_message(&mut self, message: &messaging::Message) {
+        let recipient = &mut self.friendly_units[message.recipient];
+        match message.content {
+            MessageContent::EnemyReport { enemy_position, observed_at } => {
+                if !recipient.has_discovered_enemy() || observed_at > recipient.get_known_enemy_time() {
+                    recipient.discover_enemy(&enemy_position, observed_at);
+                }
+                self.events.push(GameEvent::ReportDelivered { enemy_position, observed_at });
+            },
+            MessageContent::MoveOrder { target_position } => {
+                // a routing unit does not listen to its orders, so they count for no latency
+                if recipient.is_commandable() {
+                    recipient.set_target(&target_position);
+                    let latency = self.time - message.sent_at;
+                    self.order_latencies.push(latency);
+                    self.events.push(GameEvent::OrderDelivered { unit: UnitId::Unit(message.recipient), latency });
+                } else {
+                    self.events.push(GameEvent::OrderIgnored { unit: UnitId::Unit(message.recipient) });
+                }
+            }
+        }
     }
 
-    pub fn get_enemy_position(&self) -> &vec2d::Vec2D{
-        self.enemy.get_position()
+    fn support_morale(&mut self, dt: &f64, is_scout_visible: bool) {
+        let positions: Vec<(vec2d::Vec2D, bool)> = self.friendly_units.iter().map(|unit| (*unit.get_position(), unit.is_dead())).collect();
+
+        for (index, unit) in self.friendly_units.iter_mut().enumerate() {
+            let view_radius = unit.get_view_radius();
+            let mut nearby_allies = positions.iter().enumerate()
+                .filter(|(other, (position, is_dead))| *other != index && !is_dead && collision::are_positions_in_range(unit.get_position(), position, view_radius))
+                .count();
+            if index == COMMANDER && is_scout_visible {
+                nearby_allies += 1;
+            }
+            unit.raise_morale(dt * ALLY_MORALE_SUPPORT * nearby_allies as f64);
+        }
     }
 
-    pub fn set_player_target(&mut self, target_position: &vec2d::Vec2D){
-        self.player.set_target(target_position)
+    fn get_nearest_friendly_unit<F: Fn(&character::Character) -> bool>(&self, position: &vec2d::Vec2D, filter: F) -> Option<usize> {
+        self.friendly_units.iter().enumerate()
+            .filter(|(_, unit)| !unit.is_dead() && filter(unit))
+            .map(|(index, unit)| (index, (unit.get_position().x - position.x).powi(2) + (unit.get_position().y - position.y).powi(2)))
+            .min_by(|(_, distance1), (_, distance2)| distance1.total_cmp(distance2))
+            .map(|(index, _)| index)
     }
 
     pub fn set_scout_mission(&mut self, target_position: &vec2d::Vec2D){
-        if *self.scout.get_status() == ScoutStatus::Idle {
-            self.scout.set_status(ScoutStatus::GoingToTarget);
-            self.scout.set_position(self.player.get_position());
-            self.scout.set_mission(target_position, self.player.get_position());
+        self.order_units(&[UnitId::Scout], target_position);
+    }
+
+    pub fn order_unit(&mut self, unit: UnitId, target_position: &vec2d::Vec2D){
+        self.order_units(&[unit], target_position);
+    }
+
+    // one command to several units is a single decision
+    pub fn order_units(&mut self, units: &[UnitId], target_position: &vec2d::Vec2D){
+        let mut is_order_given = false;
+        for unit in units.iter() {
+            is_order_given |= self.give_order(*unit, target_position);
+        }
+        if is_order_given {
+            self.record_decision();
         }
     }
 
-    pub fn is_enemy_visible(&self) -> bool {
-        self.enemy.is_visible()
+    // false when the unit cannot take the order
+    fn give_order(&mut self, unit: UnitId, target_position: &vec2d::Vec2D) -> bool {
+        match unit {
+            UnitId::Unit(index) => {
+                if index >= self.friendly_units.len() {
+                    return false;
+                }
+                let commander = &self.friendly_units[COMMANDER];
+                if index == COMMANDER || is_within_earshot(commander, &self.friendly_units[index]) {
+                    self.friendly_units[index].set_target(target_position);
+                    return true;
+                }
+
+                // a detached unit only gets its orders once a messenger reaches it
+                let message = messaging::Message {
+                    content: MessageContent::MoveOrder { target_position: *target_position },
+                    sender: COMMANDER,
+                    recipient: index,
+                    sent_at: self.time
+                };
+                let commander_position = *commander.get_position();
+                self.couriers.push(messaging::Courier::new(message, &commander_position, &self.config));
+                self.events.push(GameEvent::CourierDispatched { sender: UnitId::Unit(COMMANDER), recipient: UnitId::Unit(index) });
+                true
+            },
+            UnitId::Scout => {
+                if *self.scout.get_status() != ScoutStatus::Idle {
+                    return false;
+                }
+                let commander_position = *self.friendly_units[COMMANDER].get_position();
+                self.scout.set_status(ScoutStatus::GoingToTarget);
+                self.scout.set_position(&commander_position);
+                self.scout.set_mission(target_position, &commander_position);
+                self.statistics.scout_missions_sent += 1;
+                true
+            }
+        }
     }
 
-    pub fn is_enemy_discovered(&self) -> bool {
-        self.player.has_discovered_enemy()
+    // how stale the commander's picture of the enemy was when each order went out
+    fn record_decision(&mut self) {
+        self.statistics.orders_given += 1;
+        let commander = &self.friendly_units[COMMANDER];
+        if commander.has_discovered_enemy() {
+            self.statistics.intel_ages_at_orders.push(self.time - commander.get_known_enemy_time());
+        }
     }
 
-    pub fn get_discovered_enemy_position(&self) -> &vec2d::Vec2D {
-        self.player.get_known_enemy_position()
+    pub fn snapshot(&self) -> snapshot::WorldSnapshot {
+        self.build_snapshot(false)
     }
 
-    pub fn get_player(&self) -> &character::Character {
-        &self.player
+    pub fn omniscient_snapshot(&self) -> snapshot::WorldSnapshot {
+        self.build_snapshot(true)
     }
 
-    pub fn get_enemy(&self) -> &character::Character {
-        &self.enemy
+    fn build_snapshot(&self, is_omniscient: bool) -> snapshot::WorldSnapshot {
+        let commander = &self.friendly_units[COMMANDER];
+        let mut units: Vec<snapshot::UnitView> = self.friendly_units.iter().enumerate()
+            .filter(|(_, unit)| !unit.is_dead())
+            .map(|(index, unit)| snapshot::UnitView::new(unit, Some(UnitId::Unit(index)), Side::Friendly, UnitKind::Troops, true))
+            .collect();
+
+        let scout_status = *self.scout.get_status();
+        if scout_status == ScoutStatus::Idle || self.scout.is_visible() || is_omniscient {
+            let mut scout_view = snapshot::UnitView::new(self.scout.get_character(), Some(UnitId::Scout), Side::Friendly, UnitKind::Scout, true);
+            // an idle scout waits with the commander
+            if scout_status == ScoutStatus::Idle {
+                scout_view.position = *commander.get_position();
+            }
+            scout_view.scout_status = Some(scout_status);
+            scout_view.is_carrying_report = self.scout.has_enemy_position_to_deliver();
+            units.push(scout_view);
+        }
+
+        for courier in self.couriers.iter().filter(|courier| courier.is_visible() || is_omniscient) {
+            let mut courier_view = snapshot::UnitView::new(courier.get_character(), None, Side::Friendly, UnitKind::Courier, true);
+            courier_view.is_carrying_report = matches!(courier.get_message().content, MessageContent::EnemyReport { .. });
+            units.push(courier_view);
+        }
+
+        if self.enemy.is_visible() || is_omniscient {
+            units.push(snapshot::UnitView::new(&self.enemy, None, Side::Enemy, UnitKind::Troops, is_omniscient));
+        }
+
+        let enemy_intel = if commander.has_discovered_enemy() {
+            Some(snapshot::IntelMarker { position: *commander.get_known_enemy_position(), observed_at: commander.get_known_enemy_time() })
+        } else {
+            None
+        };
+
+        let scout_route = match scout_status {
+            ScoutStatus::Idle => None,
+            ScoutStatus::GoingToTarget => Some(snapshot::ScoutRoute { target_position: Some(*self.scout.get_character().get_target_position()), return_position: *self.scout.get_return_position() }),
+            ScoutStatus::GoingToPlayer => Some(snapshot::ScoutRoute { target_position: None, return_position: *self.scout.get_return_position() })
+        };
+
+        snapshot::WorldSnapshot {
+            time: self.time,
+            tick: self.tick,
+            is_omniscient,
+            units,
+            enemy_intel,
+            scout_route,
+            orders_in_transit: self.get_orders_in_transit(),
+            average_order_latency: self.get_average_order_latency(),
+            explored_area: Rc::clone(&self.explored_area),
+            statistics: self.statistics.clone(),
+            friendly_casualties: self.friendly_units.iter().map(|unit| unit.get_max_soldiers() - unit.get_soldiers()).sum(),
+            enemy_casualties: self.enemy.get_max_soldiers() - self.enemy.get_soldiers(),
+            fight_radius: self.config.fight_radius,
+            objective: self.objective,
+            mission_end: self.mission_end,
+            outcome: self.get_outcome()
+        }
+    }
+
+    fn get_orders_in_transit(&self) -> usize {
+        self.couriers.iter().filter(|courier| matches!(courier.get_message().content, MessageContent::MoveOrder { .. })).count()
+    }
+
+    // only orders carried by a messenger are timed, from sending to the unit acting on them
+    fn get_average_order_latency(&self) -> Option<f64> {
+        if self.order_latencies.is_empty() {
+            None
+        } else {
+            Some(self.order_latencies.iter().sum::<f64>() / self.order_latencies.len() as f64)
+        }
+    }
+
+    pub fn get_outcome(&self) -> Option<Outcome> {
+        self.mission_end.map(|mission_end| mission_end.get_outcome())
+    }
+
+    pub fn get_mission_end(&self) -> Option<MissionEnd> {
+        self.mission_end
+    }
+
+    pub fn get_scenario_name(&self) -> &str {
+        &self.scenario
+    }
+
+    pub fn get_time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn get_commander_position(&self) -> Option<vec2d::Vec2D> {
+        let commander = &self.friendly_units[COMMANDER];
+        if commander.is_dead() { None } else { Some(*commander.get_position()) }
     }
 
-    pub fn get_scout(&self) -> &scout::Scout {
-        &self.scout
+    pub fn get_tick(&self) -> u64 {
+        self.tick
     }
 
     pub fn is_over(&self) -> bool {
-        *self.player.get_health() <= 0.0 || *self.enemy.get_health() <= 0.0
+        self.get_outcome().is_some()
     }
 
 
+}
+
+// the commander hears a unit as far as it sees, whichever of them is the stronger
+fn is_within_earshot(commander: &character::Character, unit: &character::Character) -> bool {
+    collision::are_positions_in_range(commander.get_position(), unit.get_position(), commander.get_view_radius())
+}
+
+fn is_in_sight(observer: &character::Character, target: &character::Character) -> bool {
+    collision::are_positions_in_range(observer.get_position(), target.get_position(), observer.get_view_radius() * target.get_concealment())
+}
+
+fn is_aware_of(observer: &character::Character, target: &character::Character) -> bool {
+    observer.has_discovered_enemy() && collision::are_positions_in_range(observer.get_known_enemy_position(), target.get_position(), observer.get_view_radius())
+}
+
+fn get_ambush_multiplier(defender: &character::Character) -> f64 {
+    if defender.is_surprised() { AMBUSH_MULTIPLIER } else { 1.0 }
+}
+
+fn push_status_events(events: &mut Vec<GameEvent>, side: Side, unit: Option<UnitId>, previous_status: &CharacterStatus, status: &CharacterStatus) {
+    if previous_status == status {
+        return;
+    }
+    match status {
+        CharacterStatus::Routing => events.push(GameEvent::UnitRouted { side, unit }),
+        CharacterStatus::Dead => events.push(GameEvent::UnitDied { side, unit }),
+        _ if *previous_status == CharacterStatus::Routing => events.push(GameEvent::UnitRallied { side, unit }),
+        _ => ()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use piston_window::{Button, Key, MouseButton};
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum PanDirection {
+    Up,
+    Down,
+    Left,
+    Right
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Action {
+    Select,
+    MoveOrder,
+    ScoutOrder,
+    PanCamera(PanDirection),
+    FollowPlayer,
+    CenterOnPlayer,
+    ToggleVision,
+    ToggleScoutRoute,
+    ToggleDebug,
+    TogglePause,
+    StepSimulation,
+    SlowDown,
+    SpeedUp,
+    SetGameSpeed(f64),
+    QuickSave,
+    QuickLoad,
+    MenuUp,
+    MenuDown,
+    MenuConfirm,
+    MenuBack
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct Binding {
+    pub button: Button,
+    pub action: Action
+}
+
+#[derive(Debug)]
+pub enum BindingsError {
+    Io(std::io::Error),
+    Format(serde_json::Error)
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingsError::Io(error) => write!(f, "{error}"),
+            BindingsError::Format(error) => write!(f, "invalid bindings: {error}")
+        }
+    }
+}
+
+impl From<std::io::Error> for BindingsError {
+    fn from(error: std::io::Error) -> Self {
+        BindingsError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for BindingsError {
+    fn from(error: serde_json::Error) -> Self {
+        BindingsError::Format(error)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct InputBindings {
+    bindings: Vec<Binding>
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let bindings = [
+            (Button::Mouse(MouseButton::Left), Action::Select),
+            (Button::Mouse(MouseButton::Right), Action::MoveOrder),
+            (Button::Mouse(MouseButton::Middle), Action::ScoutOrder),
+            (Button::Keyboard(Key::W), Action::PanCamera(PanDirection::Up)),
+            (Button::Keyboard(Key::A), Action::PanCamera(PanDirection::Left)),
+            (Button::Keyboard(Key::S), Action::PanCamera(PanDirection::Down)),
+            (Button::Keyboard(Key::D), Action::PanCamera(PanDirection::Right)),
+            (Button::Keyboard(Key::F), Action::FollowPlayer),
+            (Button::Keyboard(Key::C), Action::CenterOnPlayer),
+            (Button::Keyboard(Key::V), Action::ToggleVision),
+            (Button::Keyboard(Key::R), Action::ToggleScoutRoute),
+            (Button::Keyboard(Key::G), Action::ToggleDebug),
+            (Button::Keyboard(Key::Space), Action::TogglePause),
+            (Button::Keyboard(Key::N), Action::StepSimulation),
+            (Button::Keyboard(Key::Minus), Action::SlowDown),
+            (Button::Keyboard(Key::Equals), Action::SpeedUp),
+            (Button::Keyboard(Key::D1), Action::SetGameSpeed(0.5)),
+            (Button::Keyboard(Key::D2), Action::SetGameSpeed(1.0)),
+            (Button::Keyboard(Key::D3), Action::SetGameSpeed(2.0)),
+            (Button::Keyboard(Key::D4), Action::SetGameSpeed(4.0)),
+            (Button::Keyboard(Key::F5), Action::QuickSave),
+            (Button::Keyboard(Key::F9), Action::QuickLoad),
+            (Button::Keyboard(Key::Up), Action::MenuUp),
+            (Button::Keyboard(Key::Down), Action::MenuDown),
+            (Button::Keyboard(Key::Return), Action::MenuConfirm),
+            (Button::Keyboard(Key::Escape), Action::MenuBack)
+        ];
+        Self {
+            bindings: bindings.iter().map(|(button, action)| Binding { button: *button, action: *action }).collect()
+        }
+    }
+}
+
+impl InputBindings {
+
+    pub fn load(path: &Path) -> Result<Self, BindingsError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn get_action(&self, button: &Button) -> Option<Action> {
+        self.bindings.iter().find(|binding| binding.button == *button).map(|binding| binding.action)
+    }
+}
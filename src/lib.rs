@@ -0,0 +1,19 @@
+pub mod character;
+pub mod collision;
+pub mod scout;
+pub mod vec2d;
+pub mod game;
+pub mod enemy;
+pub mod messaging;
+pub mod save;
+pub mod snapshot;
+pub mod events;
+pub mod exploration;
+pub mod config;
+pub mod scenario;
+pub mod mission;
+pub mod after_action;
+pub mod replay;
+pub mod strategy;
+pub mod simulation;
+pub mod player;pub mod args;
@@ -5,39 +5,92 @@ extern crate opengl_graphics;
 extern crate piston;
 extern crate piston_window;
 
-use std::path::Path;
-use piston::input::{Button, ButtonArgs, ButtonEvent, ButtonState, Key};
+use std::path::{Path, PathBuf};
+use clap::Parser;
+use piston::input::{ButtonArgs, ButtonEvent, ButtonState};
 use piston::input::{ RenderEvent, UpdateArgs, UpdateEvent};
-use piston::{ MouseButton, MouseCursorEvent};
-use vec2d::Vec2D;
+use piston::{ CursorEvent, MouseCursorEvent, MouseScrollEvent};
 use graphics::{clear, math, rectangle, Context};
 use crate::graphics::{ImageSize, Transformed};
-use crate::scout::ScoutStatus;
 use piston_window::prelude::*;
 use piston_window::*;
-
-mod character;
-mod collision;
-mod scout;
-mod vec2d;
-mod game;
-mod enemy;
-
-use crate::character::CharacterStatus;
-
-const CAMERA_MOVE_SPEED: f64 = 200.0;
+use war_fog_experiment::{after_action, config, game, player, replay, save, scenario, simulation, snapshot, strategy, vec2d};
+use war_fog_experiment::vec2d::Vec2D;
+use war_fog_experiment::character::CharacterStatus;
+use war_fog_experiment::player::PlayerController;
+use war_fog_experiment::scout::ScoutStatus;
+use war_fog_experiment::snapshot::{Side, UnitKind};
+use input::{Action, PanDirection};
+
+mod selection;
+mod message_log;
+mod minimap;
+mod camera;
+mod unit_status;
+mod overlays;
+mod time_control;
+mod debug_panel;
+mod input;
+mod cli;
+mod mission_screen;
+mod review;
+mod menu;
+
+const IDLE_SCOUT_OFFSET: vec2d::Vec2D = vec2d::Vec2D{ x: 80.0, y: 40.0 };
+const SAVE_FILE_PATH: &str = "savegame.json";
+const MESSAGE_LOG_MARGIN: f64 = 20.0;
+const MESSAGE_LOG_LINE_HEIGHT: f64 = 24.0;
+const SELECTION_COLOR: [f32; 4] = [0.0, 0.8, 0.0, 1.0];
+const BINDINGS_FILE_PATH: &str = "bindings.json";
+const HEADLESS_DT: f64 = 1.0 / 60.0;
+const GAME_OVER_MENU_TOP: f64 = 600.0;
+const PAUSE_OVERLAY_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.7];
+const MENU_BACKGROUND_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+const NOTICE_COLOR: [f32; 4] = [1.0, 0.4, 0.4, 1.0];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum AppState {
+    MainMenu,
+    ScenarioSelect,
+    Briefing,
+    Playing,
+    Paused,
+    GameOver
+}
 
 pub struct App {
+    state: AppState,
+    menu: menu::Menu,
+    notice: Option<String>,
+    game: Option<game::Game>,
+    scenario: &'static scenario::Scenario,
+    game_config: config::GameConfig,
+    fixed_seed: Option<u64>,
+    bot_strategy: Option<strategy::Strategy>,
+    record_path: Option<PathBuf>,
+    replay: Option<replay::Replay>,
     cursor_position: vec2d::Vec2D,
-    camera_transform: vec2d::Vec2D,
-    camera_position: vec2d::Vec2D,
-    camera_speed: vec2d::Vec2D,
+    is_cursor_in_window: bool,
+    camera: camera::Camera,
     player_renderable: Renderable,
     player_target_renderable: Renderable,
     scout_renderable: Renderable,
+    courier_renderable: Renderable,
     map_renderable: Renderable,
     enemy_renderable: Renderable,
-    god_mode: bool,
+    debug_panel: debug_panel::DebugPanel,
+    time_control: time_control::TimeControl,
+    input_bindings: input::InputBindings,
+    replay_player: Option<replay::ReplayPlayer>,
+    bot: Option<player::Bot<strategy::StrategyPlayer>>,
+    recording: Option<(replay::Replay, PathBuf)>,
+    pending_orders: Vec<replay::ReplayOrder>,
+    timeline: after_action::Timeline,
+    review: review::Review,
+    selection: selection::Selection,
+    message_log: message_log::MessageLog,
+    minimap: minimap::Minimap,
+    overlays: overlays::Overlays,
     font: Glyphs,
     window: PistonWindow
 }
@@ -49,176 +102,701 @@ pub struct Renderable {
 }
 
 impl App {
-    fn render(&mut self, event: &Event, game: &game::Game) {
+    fn render(&mut self, event: &Event) {
+        if matches!(self.state, AppState::MainMenu | AppState::ScenarioSelect) {
+            self.render_menu(event);
+            return;
+        }
+        if let Some(game) = self.game.take() {
+            self.render_game(event, &game);
+            self.game = Some(game);
+        }
+    }
+
+    fn render_menu(&mut self, event: &Event) {
+        let title = if self.state == AppState::MainMenu { "War Fog Experiment" } else { "Select a mission" };
+        self.window.draw_2d(event, |c, gl, device| {
+            self.font.factory.encoder.flush(device);
+            clear(MENU_BACKGROUND_COLOR, gl);
+            menu::draw_title(title, &mut self.font, &c, gl);
+            self.menu.draw(&mut self.font, &c, gl);
+            if let Some(notice) = self.notice.as_ref() {
+                text(NOTICE_COLOR, 20, notice, &mut self.font, c.transform.trans(MESSAGE_LOG_MARGIN, MESSAGE_LOG_MARGIN + MESSAGE_LOG_LINE_HEIGHT), gl).unwrap();
+            }
+        });
+    }
+
+    fn render_game(&mut self, event: &Event, game: &game::Game) {
+
+        let snapshot = if self.debug_panel.is_visible() { game.omniscient_snapshot() } else { game.snapshot() };
+        let selectable_units = self.get_selectable_units(&snapshot);
+        let unit_radii: Vec<f64> = snapshot.units.iter().map(|unit| get_render_radius(self.get_unit_renderable(unit))).collect();
+        let cursor_world_position = self.get_cursor_world_position();
+        let report = if self.state == AppState::GameOver { Some(after_action::AfterActionReport::new(&snapshot)) } else { None };
 
         self.window.draw_2d(event, |c, gl, device| {
 
             self.font.factory.encoder.flush(device);
+            self.debug_panel.record_frame();
 
-            self.camera_transform.x = event.render_args().unwrap().window_size[0] / 2.0;
-            self.camera_transform.y = event.render_args().unwrap().window_size[1] / 2.0;
+            self.camera.set_viewport_size(&event.render_args().unwrap().window_size);
+            let screen_center = *self.camera.get_viewport_center();
 
-            if game.is_over() {
-                clear([0.0, 0.0, 0.0, 1.0], gl);
-                let game_over_text = if *game.get_player().get_health() > 0.0 { "You won" } else { "You lose"};
-                text([1.0, 1.0, 1.0, 1.0], 64, &game_over_text, &mut self.font, c.transform.trans(self.camera_transform.x, self.camera_transform.y), gl).unwrap();
+            if self.state == AppState::Briefing {
+                mission_screen::draw_briefing(self.scenario, &mut self.font, &c, gl);
+                return;
+            }
+            if let Some(report) = report.as_ref() {
+                mission_screen::draw_summary(&snapshot, self.scenario, report, &mut self.font, &c, gl);
+                self.review.draw(&self.timeline, &self.map_renderable.texture, &self.camera.get_viewport_size(), &mut self.font, &c, gl);
+                self.menu.draw(&mut self.font, &c, gl);
                 return;
             }
             clear([1.0, 1.0, 1.0, 1.0], gl);
 
             
 
-            let map_transform = calculate_transform(&self.map_renderable, &c, &self.camera_position, &self.camera_transform);
+            let map_transform = calculate_transform(&self.map_renderable, &c, &self.camera);
             image(&self.map_renderable.texture, map_transform, gl);
 
-            if (game.get_scout().is_visible() || self.god_mode) && *game.get_scout().get_status() != ScoutStatus::Idle {
-                self.scout_renderable.position = *game.get_scout().get_position();
-                let scout_transform = calculate_transform(&self.scout_renderable, &c, &self.camera_position, &self.camera_transform);
-                image(&self.scout_renderable.texture, scout_transform, gl);
+            self.overlays.draw(&snapshot, &self.camera, &c, gl);
+
+            for unit in snapshot.get_units_of(Side::Friendly).filter(|unit| unit.kind != UnitKind::Troops) {
+                let renderable = if unit.kind == UnitKind::Scout { &mut self.scout_renderable } else { &mut self.courier_renderable };
+                renderable.position = get_render_position(unit);
+                let unit_transform = calculate_transform(renderable, &c, &self.camera);
+                image(&renderable.texture, unit_transform, gl);
             }
 
-            if *game.get_player().get_status() == CharacterStatus::Moving || self.god_mode {
-                self.player_target_renderable.position = *game.get_player_target_position();
-                let target_transform = calculate_transform(&self.player_target_renderable, &c, &self.camera_position, &self.camera_transform);
+            for target_position in snapshot.get_units_of(Side::Friendly).filter(|unit| unit.kind == UnitKind::Troops).filter_map(|unit| unit.target_position) {
+                self.player_target_renderable.position = target_position;
+                let target_transform = calculate_transform(&self.player_target_renderable, &c, &self.camera);
                 image(&self.player_target_renderable.texture, target_transform, gl);
-
             }
 
-            if game.is_enemy_visible() || self.god_mode {
-                self.enemy_renderable.position = *game.get_enemy_position();
-                let enemy_transform = calculate_transform(&self.enemy_renderable, &c, &self.camera_position, &self.camera_transform);
+            let mut enemy_units = snapshot.get_units_of(Side::Enemy).peekable();
+            if enemy_units.peek().is_none() {
+                if let Some(enemy_intel) = snapshot.enemy_intel {
+                    self.enemy_renderable.position = enemy_intel.position;
+                    let enemy_transform = calculate_transform(&self.enemy_renderable, &c, &self.camera);
+                    let transparent_image = Image::new().color([1.0, 1.0, 1.0, 0.5]);
+                    transparent_image.draw(&self.enemy_renderable.texture, &DrawState::default(), enemy_transform, gl);
+                }
+            }
+            for enemy in enemy_units {
+                self.enemy_renderable.position = enemy.position;
+                let enemy_transform = calculate_transform(&self.enemy_renderable, &c, &self.camera);
                 image(&self.enemy_renderable.texture, enemy_transform, gl);
             }
-            else if game.is_enemy_discovered() {
-                self.enemy_renderable.position = *game.get_discovered_enemy_position();
-                let enemy_transform = calculate_transform(&self.enemy_renderable, &c, &self.camera_position, &self.camera_transform);
-                let transparent_image = Image::new().color([1.0, 1.0, 1.0, 0.5]);
-                transparent_image.draw(&self.enemy_renderable.texture, &DrawState::default(), enemy_transform, gl);
+
+            for unit in snapshot.get_units_of(Side::Friendly).filter(|unit| unit.kind == UnitKind::Troops) {
+                self.player_renderable.position = unit.position;
+                let player_transform = calculate_transform(&self.player_renderable, &c, &self.camera);
+                image(&self.player_renderable.texture, player_transform, gl);
+            }
+
+            for (unit, radius) in snapshot.units.iter().zip(unit_radii.iter()) {
+                let screen_position = self.camera.to_screen_position(&get_render_position(unit));
+                let screen_radius = radius * self.camera.get_zoom();
+                unit_status::draw(unit, &screen_position, screen_radius, &c, gl);
+            }
+
+            for unit in selectable_units.iter().filter(|unit| self.selection.is_selected(unit.id)) {
+                let screen_position = self.camera.to_screen_position(&unit.position);
+                let screen_radius = unit.radius * self.camera.get_zoom();
+                let highlight = [screen_position.x - screen_radius, screen_position.y - screen_radius, screen_radius * 2.0, screen_radius * 2.0];
+                Rectangle::new_border(SELECTION_COLOR, 2.0).draw(highlight, &DrawState::default(), c.transform, gl);
+            }
+
+            if self.selection.is_box_drag(&cursor_world_position, self.camera.get_zoom()) {
+                if let Some(drag_start) = self.selection.get_drag_start() {
+                    let start = self.camera.to_screen_position(drag_start);
+                    let end = self.camera.to_screen_position(&cursor_world_position);
+                    let drag_box = rectangle::rectangle_by_corners(start.x, start.y, end.x, end.y);
+                    Rectangle::new_border(SELECTION_COLOR, 1.0).draw(drag_box, &DrawState::default(), c.transform, gl);
+                }
+            }
+
+            let player_soldiers: u32 = snapshot.get_units_of(Side::Friendly).filter(|unit| unit.kind == UnitKind::Troops).map(|unit| unit.soldiers).sum();
+            let player_max_soldiers: u32 = snapshot.get_units_of(Side::Friendly).filter(|unit| unit.kind == UnitKind::Troops).map(|unit| unit.max_soldiers).sum();
+            text([0.0, 0.0, 0.0, 1.0], 32, &format!("Men : {player_soldiers} / {player_max_soldiers}"), &mut self.font, c.transform.trans(screen_center.x * 0.1, screen_center.y * 1.9), gl).unwrap();
+
+            if let Some(commander) = snapshot.get_unit(game::UnitId::Unit(game::COMMANDER)) {
+                let player_morale = commander.morale.unwrap_or_default().round();
+                let morale_text = if commander.status == CharacterStatus::Routing { format!("Morale : {player_morale} (routing)") } else { format!("Morale : {player_morale}") };
+                text([0.0, 0.0, 0.0, 1.0], 32, &morale_text, &mut self.font, c.transform.trans(screen_center.x * 0.6, screen_center.y * 1.9), gl).unwrap();
+            }
+
+            let objective_text = match snapshot.objective.get_time_limit() {
+                Some(time_limit) => format!("Objective : {} ({} left)", snapshot.objective.get_description(), message_log::format_game_time(time_limit - snapshot.time)),
+                None => format!("Objective : {}", snapshot.objective.get_description())
+            };
+            text([0.0, 0.0, 0.0, 1.0], 24, &objective_text, &mut self.font, c.transform.trans(screen_center.x * 0.1, screen_center.y * 1.7), gl).unwrap();
+
+            if snapshot.orders_in_transit > 0 {
+                let orders_in_transit = snapshot.orders_in_transit;
+                text([0.0, 0.0, 0.0, 1.0], 32, &format!("Orders en route : {orders_in_transit}"), &mut self.font, c.transform.trans(screen_center.x * 0.1, screen_center.y * 1.8), gl).unwrap();
+            }
+
+            if let Some(bot) = self.bot.as_ref() {
+                text([0.0, 0.0, 0.0, 1.0], 32, &format!("Spectating : {}", bot.get_controller().get_name()), &mut self.font, c.transform.trans(screen_center.x * 0.9, MESSAGE_LOG_MARGIN + 64.0), gl).unwrap();
+            }
+
+            if self.time_control.is_paused() || self.time_control.get_time_scale() != 1.0 {
+                let speed_text = if self.time_control.is_paused() { "Paused".to_string() } else { format!("Speed x{}", self.time_control.get_time_scale()) };
+                text([0.0, 0.0, 0.0, 1.0], 32, &speed_text, &mut self.font, c.transform.trans(screen_center.x * 0.9, MESSAGE_LOG_MARGIN + 32.0), gl).unwrap();
+            }
+
+            self.minimap.draw(&snapshot, &self.map_renderable.texture, &self.camera, &c, gl);
+
+            for (line, (entry, opacity)) in self.message_log.get_visible_entries(snapshot.time).enumerate() {
+                let timestamp = message_log::format_game_time(entry.time);
+                let message_text = format!("[{timestamp}] {}", entry.text);
+                let message_transform = c.transform.trans(MESSAGE_LOG_MARGIN, MESSAGE_LOG_MARGIN + MESSAGE_LOG_LINE_HEIGHT * (line + 1) as f64);
+                text([0.0, 0.0, 0.0, opacity as f32], 20, &message_text, &mut self.font, message_transform, gl).unwrap();
             }
 
-            self.player_renderable.position = *game.get_player_position();
-            let player_transform = calculate_transform(&self.player_renderable, &c, &self.camera_position, &self.camera_transform);
-            image(&self.player_renderable.texture, player_transform, gl);
+            self.debug_panel.draw(&snapshot, &self.time_control, &mut self.font, &c, gl);
 
-            let player_health = game.get_player().get_health();
-            text([0.0, 0.0, 0.0, 1.0], 32, &format!("Health"), &mut self.font, c.transform.trans(self.camera_transform.x * 0.1, self.camera_transform.y * 1.9), gl).unwrap();
-            let health_rectangle = rectangle::rectangle_by_corners(0.0, 0.0, *player_health, 20.0);
-            let health_loss_rectange = rectangle::rectangle_by_corners(0.0, 0.0, 100.0 - *player_health, 20.0);
-            rectangle([0.0, 0.8, 0.0, 1.0], health_rectangle, c.transform.trans(self.camera_transform.x * 0.3, (self.camera_transform.y * 1.9) - 20.0), gl);
-            rectangle([0.8, 0.0, 0.0, 1.0], health_loss_rectange, c.transform.trans((self.camera_transform.x * 0.3) + *player_health, (self.camera_transform.y * 1.9) - 20.0), gl);
+            if snapshot.is_omniscient {
+                if let Some(enemy) = snapshot.get_units_of(Side::Enemy).next() {
+                    let enemy_soldiers = enemy.soldiers;
+                    let enemy_morale = enemy.morale.unwrap_or_default().round();
+                    text([0.0, 0.0, 0.0, 1.0], 32, &format!("Enemy men : {enemy_soldiers}, morale : {enemy_morale}"), &mut self.font, c.transform.trans(screen_center.x * 1.5, screen_center.y * 1.9), gl).unwrap(); 
+                }
 
-            if self.god_mode {
-                let enemy_health = game.get_enemy().get_health();
-                text([0.0, 0.0, 0.0, 1.0], 32, &format!("Enemy health : {enemy_health}"), &mut self.font, c.transform.trans(self.camera_transform.x * 1.5, self.camera_transform.y * 1.9), gl).unwrap(); 
+                if let Some(average_order_latency) = snapshot.average_order_latency {
+                    let average_latency = average_order_latency.round();
+                    text([0.0, 0.0, 0.0, 1.0], 32, &format!("Average order delay : {average_latency}s"), &mut self.font, c.transform.trans(screen_center.x * 1.5, screen_center.y * 1.8), gl).unwrap();
+                }
             }
 
+            if self.state == AppState::Paused {
+                let window_size = self.camera.get_viewport_size();
+                rectangle(PAUSE_OVERLAY_COLOR, [0.0, 0.0, window_size.x, window_size.y], c.transform, gl);
+                menu::draw_title("Paused", &mut self.font, &c, gl);
+                self.menu.draw(&mut self.font, &c, gl);
+            }
         });
     }
 
-    
+    fn update(&mut self, args: &UpdateArgs) {
+        if self.state != AppState::Playing {
+            return;
+        }
+        let Some(mut game) = self.game.take() else {
+            return;
+        };
+        self.update_game(args, &mut game);
+        if game.is_over() {
+            self.finish_recording();
+            self.show_menu(AppState::GameOver);
+        }
+        self.game = Some(game);
+    }
 
-    fn update(&mut self, args: &UpdateArgs, game: &mut game::Game) {
+    fn update_game(&mut self, args: &UpdateArgs, game: &mut game::Game) {
+        for dt in self.time_control.get_steps(args.dt) {
+            let events = match self.replay_player.as_mut() {
+                Some(player) => player.advance(game, dt),
+                None => {
+                    if let Some(bot) = self.bot.as_mut() {
+                        run_bot(bot, game, self.recording.is_some().then_some(&mut self.pending_orders));
+                    }
+                    if let Some((replay, _)) = self.recording.as_mut() {
+                        replay.record_step(dt, std::mem::take(&mut self.pending_orders));
+                    }
+                    game.update(&dt)
+                }
+            };
+            self.message_log.record_events(&events, game.get_time());
+            self.timeline.record(game);
+        }
 
-        game.update(&args.dt);
+        let cursor_position = if self.is_cursor_in_window { Some(&self.cursor_position) } else { None };
+        let player_position = game.get_commander_position();
+        self.camera.update(&args.dt, cursor_position, player_position);
+    }
 
-        self.camera_position.x += self.camera_speed.x * args.dt;
-        self.camera_position.y += self.camera_speed.y * args.dt;
+    fn react_to_inputs(&mut self, args: &ButtonArgs) {
+        // the briefing waits for any key, bound or not
+        if self.state == AppState::Briefing {
+            if args.state == ButtonState::Press {
+                self.state = AppState::Playing;
+            }
+            return;
+        }
+        let Some(action) = self.input_bindings.get_action(&args.button) else {
+            return;
+        };
+        match self.state {
+            AppState::Playing if action == Action::MenuBack => {
+                if args.state == ButtonState::Press {
+                    self.show_menu(AppState::Paused);
+                }
+            },
+            AppState::Playing => {
+                if let Some(mut game) = self.game.take() {
+                    self.react_to_game_inputs(action, args.state, &mut game);
+                    self.game = Some(game);
+                }
+            },
+            AppState::GameOver => {
+                if !self.react_to_menu_inputs(action, args.state) {
+                    self.react_to_review_inputs(action, args.state);
+                }
+            },
+            _ => {
+                self.react_to_menu_inputs(action, args.state);
+            }
+        }
     }
 
-    fn react_to_inputs(&mut self, args: &ButtonArgs, game: &mut game::Game) {
-        match args.state {
+    fn react_to_game_inputs(&mut self, action: Action, state: ButtonState, game: &mut game::Game) {
+        match state {
             ButtonState::Press => {
-                match args.button {
-                    Button::Mouse(MouseButton::Left) => {
-                        let cursor_world_position = self.get_cursor_world_position();
-                        game.set_player_target(&cursor_world_position);
-
+                match action {
+                    Action::Select => {
+                        if let Some(minimap_world_position) = self.minimap.to_world_position(&self.cursor_position, &self.camera.get_viewport_size()) {
+                            self.camera.center_on(&minimap_world_position);
+                        } else {
+                            let cursor_world_position = self.get_cursor_world_position();
+                            self.selection.begin_drag(&cursor_world_position);
+                        }
                     }
-                    Button::Mouse(MouseButton::Right) => {
+                    Action::MoveOrder => {
                         let cursor_world_position = self.get_cursor_world_position();
-                        game.set_scout_mission(&cursor_world_position);
+                        let units = self.selection.get_units().to_vec();
+                        self.order_units(game, &units, &cursor_world_position);
                     }
-                    Button::Keyboard(Key::W) => {
-                        self.camera_speed.y =
-                            num::clamp(self.camera_speed.y - CAMERA_MOVE_SPEED, -CAMERA_MOVE_SPEED, CAMERA_MOVE_SPEED)
+                    Action::ScoutOrder => {
+                        let cursor_world_position = self.get_cursor_world_position();
+                        self.order_units(game, &[game::UnitId::Scout], &cursor_world_position);
                     }
-                    Button::Keyboard(Key::A) => {
-                        self.camera_speed.x =
-                            num::clamp(self.camera_speed.x - CAMERA_MOVE_SPEED, -CAMERA_MOVE_SPEED, CAMERA_MOVE_SPEED)
+                    Action::PanCamera(direction) => self.camera.pan(&get_pan_vector(direction)),
+                    Action::FollowPlayer => self.camera.toggle_follow(),
+                    Action::CenterOnPlayer => {
+                        if let Some(commander_position) = game.get_commander_position() {
+                            self.camera.center_on(&commander_position);
+                        }
                     }
-                    Button::Keyboard(Key::S) => {
-                        self.camera_speed.y =
-                            num::clamp(self.camera_speed.y + CAMERA_MOVE_SPEED, -CAMERA_MOVE_SPEED, CAMERA_MOVE_SPEED)
+                    Action::ToggleVision => self.overlays.toggle_vision(),
+                    Action::ToggleScoutRoute => self.overlays.toggle_route(),
+                    Action::ToggleDebug => self.debug_panel.toggle(),
+                    Action::TogglePause => self.time_control.toggle_pause(),
+                    Action::StepSimulation => self.time_control.step(),
+                    Action::SlowDown => self.time_control.slow_down(),
+                    Action::SpeedUp => self.time_control.speed_up(),
+                    Action::SetGameSpeed(time_scale) => self.time_control.set_time_scale(time_scale),
+                    Action::QuickSave => {
+                        match save::save_game(game, Path::new(SAVE_FILE_PATH)) {
+                            Ok(()) => self.message_log.push("Game saved".to_string(), game.get_time()),
+                            Err(error) => self.message_log.push(format!("Could not save game: {error}"), game.get_time())
+                        }
                     }
-                    Button::Keyboard(Key::D) => {
-                        self.camera_speed.x =
-                            num::clamp(self.camera_speed.x + CAMERA_MOVE_SPEED, -CAMERA_MOVE_SPEED, CAMERA_MOVE_SPEED)
-                    },
-                    Button::Keyboard(Key::G) => {
-                        self.god_mode = true
+                    Action::QuickLoad => {
+                        match save::load_game(Path::new(SAVE_FILE_PATH)) {
+                            Ok(loaded_game) => {
+                                *game = loaded_game;
+                                self.reset_game_ui(game, None, None);
+                                self.message_log.push("Game loaded".to_string(), game.get_time());
+                            },
+                            Err(error) => self.message_log.push(format!("Could not load game: {error}"), game.get_time())
+                        }
                     }
-                    _ => (),
+                    Action::MenuUp | Action::MenuDown | Action::MenuConfirm | Action::MenuBack => ()
                 }
             }
-            ButtonState::Release => match args.button {
-                Button::Keyboard(Key::W) => self.camera_speed.y = 0.0,
-                Button::Keyboard(Key::A) => self.camera_speed.x = 0.0,
-                Button::Keyboard(Key::S) => self.camera_speed.y = 0.0,
-                Button::Keyboard(Key::D) => self.camera_speed.x = 0.0,
-                Button::Keyboard(Key::G) => self.god_mode = false,
+            ButtonState::Release => match action {
+                Action::Select => {
+                    let cursor_world_position = self.get_cursor_world_position();
+                    let selectable_units = self.get_selectable_units(&game.snapshot());
+                    self.selection.end_drag(&cursor_world_position, self.camera.get_zoom(), &selectable_units);
+                }
+                Action::PanCamera(PanDirection::Up | PanDirection::Down) => self.camera.stop_vertical_pan(),
+                Action::PanCamera(PanDirection::Left | PanDirection::Right) => self.camera.stop_horizontal_pan(),
                 _ => (),
             },
         }
     }
 
-    fn get_cursor_world_position(&mut self) -> Vec2D {
-        Vec2D {
-            x: self.cursor_position.x - self.camera_transform.x + self.camera_position.x,
-            y: self.cursor_position.y - self.camera_transform.y + self.camera_position.y,
+    // on the game over screen, clicks outside the menu and sideways panning scrub the timeline
+    fn react_to_review_inputs(&mut self, action: Action, state: ButtonState) {
+        let sample_count = self.timeline.get_samples().len();
+        match (action, state) {
+            (Action::Select, ButtonState::Press) => self.review.begin_scrub(&self.cursor_position, &self.camera.get_viewport_size(), sample_count),
+            (Action::Select, ButtonState::Release) => self.review.end_scrub(),
+            (Action::PanCamera(PanDirection::Left), ButtonState::Press) => self.review.step(-1, sample_count),
+            (Action::PanCamera(PanDirection::Right), ButtonState::Press) => self.review.step(1, sample_count),
+            _ => ()
         }
     }
 
+    // returns whether the menu used the input
+    fn react_to_menu_inputs(&mut self, action: Action, state: ButtonState) -> bool {
+        if state != ButtonState::Press {
+            return false;
+        }
+        match action {
+            Action::MenuUp => self.menu.select_previous(),
+            Action::MenuDown => self.menu.select_next(),
+            Action::MenuConfirm => self.confirm_menu_item(self.menu.get_selected()),
+            Action::MenuBack => self.go_back(),
+            Action::Select => match self.menu.select_at(&self.cursor_position) {
+                Some(item) => self.confirm_menu_item(item),
+                None => return false
+            },
+            _ => return false
+        }
+        true
+    }
+
+    fn confirm_menu_item(&mut self, item: menu::MenuItem) {
+        self.notice = None;
+        match item {
+            menu::MenuItem::StartMission => self.show_menu(AppState::ScenarioSelect),
+            menu::MenuItem::LoadGame => self.load_saved_game(),
+            menu::MenuItem::Scenario(index) => self.start_new_game(&scenario::SCENARIOS[index]),
+            menu::MenuItem::Resume => self.state = AppState::Playing,
+            menu::MenuItem::Restart => self.restart(),
+            menu::MenuItem::MainMenu => {
+                self.finish_recording();
+                self.game = None;
+                self.show_menu(AppState::MainMenu);
+            },
+            menu::MenuItem::Quit => self.window.set_should_close(true)
+        }
+    }
+
+    fn go_back(&mut self) {
+        match self.state {
+            AppState::MainMenu => self.window.set_should_close(true),
+            AppState::Paused => self.state = AppState::Playing,
+            AppState::ScenarioSelect | AppState::GameOver => self.confirm_menu_item(menu::MenuItem::MainMenu),
+            AppState::Briefing | AppState::Playing => ()
+        }
+    }
+
+    fn show_menu(&mut self, state: AppState) {
+        let items = match state {
+            AppState::MainMenu => vec![menu::MenuItem::StartMission, menu::MenuItem::LoadGame, menu::MenuItem::Quit],
+            AppState::ScenarioSelect => (0..scenario::SCENARIOS.len()).map(menu::MenuItem::Scenario).chain([menu::MenuItem::MainMenu]).collect(),
+            AppState::Paused => vec![menu::MenuItem::Resume, menu::MenuItem::Restart, menu::MenuItem::MainMenu, menu::MenuItem::Quit],
+            AppState::GameOver => vec![menu::MenuItem::Restart, menu::MenuItem::MainMenu, menu::MenuItem::Quit],
+            AppState::Briefing | AppState::Playing => Vec::new()
+        };
+        let top = if state == AppState::GameOver { GAME_OVER_MENU_TOP } else { menu::MENU_TOP };
+        self.menu = menu::Menu::new(items, top);
+        if let Some(index) = scenario::SCENARIOS.iter().position(|other| other.name == self.scenario.name) {
+            self.menu.select(menu::MenuItem::Scenario(index));
+        }
+        self.state = state;
+    }
+
+    fn start_new_game(&mut self, scenario: &'static scenario::Scenario) {
+        let seed = self.fixed_seed.unwrap_or_else(rand::random);
+        let game = game::Game::with_scenario(scenario, self.game_config.clone(), seed);
+        self.begin_game(game, None, Some(seed));
+    }
+
+    // a replay being watched restarts from its beginning, anything else as a new game of the same scenario
+    fn restart(&mut self) {
+        let replay = if self.replay_player.is_some() { self.replay.clone() } else { None };
+        match replay.and_then(|replay| replay.create_game().map(|game| (game, replay))) {
+            Some((game, replay)) => self.begin_game(game, Some(replay::ReplayPlayer::new(replay)), None),
+            None => self.start_new_game(self.scenario)
+        }
+    }
+
+    fn load_saved_game(&mut self) {
+        match save::load_game(Path::new(SAVE_FILE_PATH)) {
+            Ok(game) => {
+                self.begin_game(game, None, None);
+                self.state = AppState::Playing;
+            },
+            Err(error) => self.notice = Some(format!("Could not load game: {error}"))
+        }
+    }
+
+    // only a fresh game has a seed, and only a fresh game is recorded
+    fn begin_game(&mut self, game: game::Game, replay_player: Option<replay::ReplayPlayer>, seed: Option<u64>) {
+        self.reset_game_ui(&game, replay_player, seed);
+        self.game = Some(game);
+        self.state = AppState::Briefing;
+    }
+
+    fn reset_game_ui(&mut self, game: &game::Game, replay_player: Option<replay::ReplayPlayer>, seed: Option<u64>) {
+        self.finish_recording();
+        self.scenario = scenario::find_scenario(game.get_scenario_name()).unwrap_or(scenario::get_default_scenario());
+        self.recording = match (seed, self.record_path.as_ref()) {
+            (Some(seed), Some(path)) if replay_player.is_none() => Some((replay::Replay::new(self.scenario.name, seed, self.game_config.clone()), path.clone())),
+            _ => None
+        };
+        self.bot = match (&replay_player, self.bot_strategy) {
+            (None, Some(bot_strategy)) => Some(player::Bot::new(strategy::StrategyPlayer::new(bot_strategy, seed.unwrap_or_else(rand::random)))),
+            _ => None
+        };
+        self.replay_player = replay_player;
+        self.pending_orders.clear();
+        self.timeline = after_action::Timeline::new();
+        self.review.reset();
+        self.selection = selection::Selection::new();
+        self.message_log.clear();
+        self.time_control = time_control::TimeControl::new();
+        if let Some(commander_position) = game.get_commander_position() {
+            self.camera.center_on(&commander_position);
+        }
+    }
+
+    // orders are ignored while a replay plays or a bot commands, they bring their own
+    fn order_units(&mut self, game: &mut game::Game, units: &[game::UnitId], target_position: &Vec2D) {
+        if self.replay_player.is_some() || self.bot.is_some() || units.is_empty() {
+            return;
+        }
+        game.order_units(units, target_position);
+        if self.recording.is_some() {
+            self.pending_orders.push(replay::ReplayOrder { units: units.to_vec(), target_position: *target_position });
+        }
+    }
+
+    // a loaded save has a history the replay never saw, so recording stops there
+    fn finish_recording(&mut self) {
+        if let Some((replay, path)) = self.recording.take() {
+            match save::save_replay(&replay, &path) {
+                Ok(()) => println!("Replay saved to {}", path.display()),
+                Err(error) => eprintln!("Could not save replay: {error}")
+            }
+        }
+    }
+
+    fn get_selectable_units(&self, snapshot: &snapshot::WorldSnapshot) -> Vec<selection::SelectableUnit> {
+        snapshot.get_units_of(Side::Friendly)
+            .filter(|unit| unit.kind != UnitKind::Courier)
+            .filter_map(|unit| {
+                let radius = get_render_radius(self.get_unit_renderable(unit));
+                unit.id.map(|id| selection::SelectableUnit { id, position: get_render_position(unit), radius })
+            })
+            .collect()
+    }
+
+    fn get_unit_renderable(&self, unit: &snapshot::UnitView) -> &Renderable {
+        match (unit.side, unit.kind) {
+            (Side::Enemy, _) => &self.enemy_renderable,
+            (Side::Friendly, UnitKind::Troops) => &self.player_renderable,
+            (Side::Friendly, UnitKind::Scout) => &self.scout_renderable,
+            (Side::Friendly, UnitKind::Courier) => &self.courier_renderable
+        }
+    }
+
+    fn get_cursor_world_position(&self) -> Vec2D {
+        self.camera.to_world_position(&self.cursor_position)
+    }
+
     fn update_cursor_position(&mut self, args: &[f64]) {
         self.cursor_position.x = args[0];
         self.cursor_position.y = args[1];
+        self.review.scrub(&self.cursor_position, &self.camera.get_viewport_size(), self.timeline.get_samples().len());
+    }
+
+    fn zoom_camera(&mut self, args: &[f64]) {
+        self.camera.zoom_at(&self.cursor_position, args[1]);
     }
 }
 
-fn calculate_transform(renderable: &Renderable, c: &Context, camera_position: &vec2d::Vec2D, camera_transform: &vec2d::Vec2D) -> math::Matrix2d {
+fn calculate_transform(renderable: &Renderable, c: &Context, camera: &camera::Camera) -> math::Matrix2d {
+    let camera_position = camera.get_position();
+    let viewport_center = camera.get_viewport_center();
+    let zoom = camera.get_zoom();
     c
         .transform
+        .trans(viewport_center.x, viewport_center.y)
+        .zoom(zoom)
+        .trans(- camera_position.x, - camera_position.y)
         .trans(renderable.position.x, renderable.position.y)
         .trans(
             renderable.texture.get_width() as f64 * -0.5 * renderable.size,
             renderable.texture.get_height() as f64 * -0.5 * renderable.size,
         )
-        .trans(- camera_position.x, - camera_position.y)
-        .trans(camera_transform.x, camera_transform.y)
         .scale(renderable.size, renderable.size)
 }
 
+fn get_render_radius(renderable: &Renderable) -> f64 {
+    renderable.texture.get_width() as f64 * 0.5 * renderable.size
+}
+
+fn get_pan_vector(direction: PanDirection) -> vec2d::Vec2D {
+    match direction {
+        PanDirection::Up => vec2d::Vec2D{ x: 0.0, y: -1.0 },
+        PanDirection::Down => vec2d::Vec2D{ x: 0.0, y: 1.0 },
+        PanDirection::Left => vec2d::Vec2D{ x: -1.0, y: 0.0 },
+        PanDirection::Right => vec2d::Vec2D{ x: 1.0, y: 0.0 }
+    }
+}
+
+// an idle scout waits beside the player instead of on top of it so it can be picked out
+fn get_render_position(unit: &snapshot::UnitView) -> vec2d::Vec2D {
+    if unit.scout_status == Some(ScoutStatus::Idle) {
+        return vec2d::Vec2D {
+            x: unit.position.x + IDLE_SCOUT_OFFSET.x,
+            y: unit.position.y + IDLE_SCOUT_OFFSET.y
+        };
+    }
+    unit.position
+}
+
+// orders go through the recorder when there is one, so replays carry the bot's decisions
+fn run_bot(bot: &mut player::Bot<strategy::StrategyPlayer>, game: &mut game::Game, recorded_orders: Option<&mut Vec<replay::ReplayOrder>>) {
+    if !bot.is_due(game.get_time()) {
+        return;
+    }
+    let time = game.get_time();
+    let snapshot = game.snapshot();
+    match recorded_orders {
+        Some(orders) => bot.act(time, &snapshot, &mut replay::RecordingCommands { game, orders }),
+        None => bot.act(time, &snapshot, game)
+    }
+}
+
+fn load_config(cli: &cli::Cli) -> Result<config::GameConfig, config::ConfigError> {
+    let mut game_config = cli.game.load_config()?;
+    if let Some(width) = cli.width {
+        game_config.window_width = width;
+    }
+    if let Some(height) = cli.height {
+        game_config.window_height = height;
+    }
+    game_config.validate()?;
+    Ok(game_config)
+}
+
+fn exit_with_error(message: String) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
+}
+
+// plays the game at a fixed step with a bot or nobody giving orders, or follows a replay
+fn run_headless(game: &mut game::Game, mut replay_player: Option<replay::ReplayPlayer>, mut bot: Option<player::Bot<strategy::StrategyPlayer>>, recording: Option<(replay::Replay, PathBuf)>, duration: f64) {
+    let mut recording = recording;
+    while !game.is_over() && game.get_time() < duration {
+        match replay_player.as_mut() {
+            Some(player) if player.is_finished() => break,
+            Some(player) => { player.advance(game, HEADLESS_DT); }
+            None => {
+                let mut orders = Vec::new();
+                if let Some(bot) = bot.as_mut() {
+                    run_bot(bot, game, recording.is_some().then_some(&mut orders));
+                }
+                if let Some((replay, _)) = recording.as_mut() {
+                    replay.record_step(HEADLESS_DT, orders);
+                }
+                game.update(&HEADLESS_DT);
+            }
+        }
+    }
+
+    println!("Outcome : {} after {} ({} ticks)", simulation::format_outcome(game.get_outcome()), message_log::format_game_time(game.get_time()), game.get_tick());
+    if let Some(mission_end) = game.get_mission_end() {
+        println!("{}", mission_end.get_description());
+    }
+    print_after_action_report(&after_action::AfterActionReport::new(&game.snapshot()));
+
+    if let Some((replay, path)) = recording {
+        if let Err(error) = save::save_replay(&replay, &path) {
+            exit_with_error(format!("Could not save replay: {error}"));
+        }
+    }
+}
+
+fn print_after_action_report(report: &after_action::AfterActionReport) {
+    println!("Distance marched : {:.0}", report.distance_travelled);
+    println!("Scout missions : {} sent, {} returned", report.scout_missions_sent, report.scout_missions_returned);
+    println!("Orders given : {}, {} without any intel", report.orders_given, report.orders_without_intel);
+    if let Some(mean_intel_age) = report.mean_intel_age {
+        println!("Average intel age at orders : {mean_intel_age:.1}s");
+    }
+    if let Some(mean_order_latency) = report.mean_order_latency {
+        println!("Average order delay by messenger : {mean_order_latency:.1}s");
+    }
+    println!("Men lost : {}, enemy casualties : {}", report.soldiers_lost, report.enemy_soldiers_killed);
+}
+
 fn main() {
-    let mut window: PistonWindow = WindowSettings::new("War Fog Experiment", [1280, 1080])
-        .exit_on_esc(true)
+    let cli = cli::Cli::parse();
+    let game_config = load_config(&cli).unwrap_or_else(|error| exit_with_error(format!("Could not load config: {error}")));
+    let scenario = cli.game.get_scenario();
+    let bot_strategy = cli.bot.as_deref().and_then(|kind| strategy::Strategy::from_kind(kind, cli.scout_interval));
+    let replay = cli.replay.as_ref().map(|path| save::load_replay(path).unwrap_or_else(|error| exit_with_error(format!("Could not load replay: {error}"))));
+    if let Some(replay) = replay.as_ref() {
+        if scenario::find_scenario(replay.get_scenario()).is_none() {
+            exit_with_error(format!("Unknown scenario {} in replay", replay.get_scenario()));
+        }
+    }
+
+    if cli.headless {
+        let seed = cli.seed.unwrap_or_else(rand::random);
+        let (mut game, replay_player) = match replay {
+            Some(replay) => (replay.create_game().unwrap(), Some(replay::ReplayPlayer::new(replay))),
+            None => (game::Game::with_scenario(scenario, game_config.clone(), seed), None)
+        };
+        let bot = bot_strategy.map(|bot_strategy| player::Bot::new(strategy::StrategyPlayer::new(bot_strategy, seed)));
+        let recording = cli.record.clone().map(|path| (replay::Replay::new(scenario.name, seed, game_config.clone()), path));
+        run_headless(&mut game, replay_player, bot, recording, cli.duration);
+        return;
+    }
+
+    // escape opens the pause menu instead, quitting goes through the menus
+    let mut window: PistonWindow = WindowSettings::new("War Fog Experiment", [game_config.window_width, game_config.window_height])
+        .fullscreen(cli.fullscreen)
+        .exit_on_esc(false)
         .build()
         .unwrap();
 
-    let mut game = game::Game::new();
-
     let mut texture_context = TextureContext {
         factory: window.factory.clone(),
         encoder: window.factory.create_command_buffer().into()
     };
 
+    // a missing bindings file keeps the default controls, a broken one is reported and ignored
+    let bindings_path = Path::new(BINDINGS_FILE_PATH);
+    let input_bindings = if bindings_path.exists() {
+        input::InputBindings::load(bindings_path).unwrap_or_else(|error| {
+            eprintln!("Could not load {BINDINGS_FILE_PATH}, using default controls: {error}");
+            input::InputBindings::default()
+        })
+    } else {
+        input::InputBindings::default()
+    };
+
     let font = window.load_font(Path::new("assets/TheanoDidot-Regular.ttf")).unwrap();
 
-    // Create a new game and run it.
+    let map_renderable = Renderable {
+        position: vec2d::new(),
+        texture: Texture::from_path(&mut texture_context, &cli.map, Flip::None, &TextureSettings::new())
+            .unwrap_or_else(|error| exit_with_error(format!("Could not load map {}: {error}", cli.map.display()))),
+        size: 1.0
+    };
+    let map_size = vec2d::Vec2D {
+        x: map_renderable.texture.get_width() as f64 * map_renderable.size,
+        y: map_renderable.texture.get_height() as f64 * map_renderable.size
+    };
+    let minimap = minimap::Minimap::new(map_size);
+    let review = review::Review::new(map_size, GAME_OVER_MENU_TOP);
+
     let mut app = App {
+        state: AppState::MainMenu,
+        menu: menu::Menu::new(Vec::new(), menu::MENU_TOP),
+        notice: None,
+        game: None,
+        scenario,
+        game_config: game_config.clone(),
+        fixed_seed: cli.seed,
+        bot_strategy,
+        record_path: cli.record.clone(),
+        replay,
         window,
         cursor_position: vec2d::new(),
-        camera_transform: vec2d::new(),
-        camera_position: vec2d::new(),
-        camera_speed: vec2d::new(),
+        is_cursor_in_window: false,
+        camera: camera::Camera::new(map_size, game_config.camera_move_speed),
         player_renderable: Renderable {
             position: vec2d::new(),
             texture: Texture::from_path(&mut texture_context, Path::new("./assets/rust.png"), Flip::None, &TextureSettings::new()).unwrap(),
@@ -234,34 +812,63 @@ fn main() {
             texture: Texture::from_path(&mut texture_context, Path::new("./assets/scout.png"), Flip::None, &TextureSettings::new()).unwrap(),
             size: 0.25
         },
-        map_renderable: Renderable {
+        courier_renderable: Renderable {
             position: vec2d::new(),
-            texture: Texture::from_path(&mut texture_context, Path::new("./assets/map_2.jpg"), Flip::None, &TextureSettings::new()).unwrap(),
-            size: 1.0
+            texture: Texture::from_path(&mut texture_context, Path::new("./assets/scout.png"), Flip::None, &TextureSettings::new()).unwrap(),
+            size: 0.15
         },
+        map_renderable,
         enemy_renderable: Renderable {
             position: vec2d::new(),
             texture: Texture::from_path(&mut texture_context, Path::new("./assets/enemy.png"), Flip::None, &TextureSettings::new()).unwrap(),
             size: 0.25
         },
-        god_mode: false,
+        debug_panel: debug_panel::DebugPanel::new(),
+        time_control: time_control::TimeControl::new(),
+        input_bindings,
+        replay_player: None,
+        bot: None,
+        recording: None,
+        pending_orders: Vec::new(),
+        timeline: after_action::Timeline::new(),
+        review,
+        selection: selection::Selection::new(),
+        message_log: message_log::MessageLog::new(),
+        minimap,
+        overlays: overlays::Overlays::new(),
         font
     };
 
+    // a replay or a spectated bot goes straight to its mission, a player starts at the main menu
+    if app.replay.is_some() {
+        app.restart();
+    } else if app.bot_strategy.is_some() {
+        app.start_new_game(scenario);
+    } else {
+        app.show_menu(AppState::MainMenu);
+    }
+
     while let Some(e) = app.window.next() {
 
-        app.render(&e, &game);
+        app.render(&e);
         
         if let Some(args) = e.update_args() {
-            app.update(&args, &mut game);
+            app.update(&args);
         }
 
         if let Some(args) = e.button_args() {
-            app.react_to_inputs(&args, &mut game);
+            app.react_to_inputs(&args);
         }
         if let Some(args) = e.mouse_cursor_args() {
             app.update_cursor_position(&args);
         }
+        if let Some(args) = e.mouse_scroll_args() {
+            app.zoom_camera(&args);
+        }
+        if let Some(is_in_window) = e.cursor_args() {
+            app.is_cursor_in_window = is_in_window;
+        }
     }
+    app.finish_recording();
     
 }
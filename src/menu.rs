@@ -0,0 +1,104 @@
+use piston_window::*;
+use war_fog_experiment::scenario;
+use war_fog_experiment::vec2d;
+
+pub const MENU_TOP: f64 = 220.0;
+const MENU_LEFT: f64 = 100.0;
+const TITLE_TOP: f64 = 160.0;
+const ITEM_HEIGHT: f64 = 44.0;
+const ITEM_FONT_SIZE: u32 = 28;
+const TITLE_FONT_SIZE: u32 = 56;
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const SELECTED_COLOR: [f32; 4] = [1.0, 0.8, 0.0, 1.0];
+const HINT_COLOR: [f32; 4] = [0.7, 0.7, 0.7, 1.0];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MenuItem {
+    StartMission,
+    LoadGame,
+    Scenario(usize),
+    Resume,
+    Restart,
+    MainMenu,
+    Quit
+}
+
+impl MenuItem {
+
+    pub fn get_label(&self) -> String {
+        match self {
+            MenuItem::StartMission => "Start a mission".to_string(),
+            MenuItem::LoadGame => "Load saved game".to_string(),
+            MenuItem::Scenario(index) => {
+                let scenario = &scenario::SCENARIOS[*index];
+                format!("{} : {}", scenario.name, scenario.description)
+            },
+            MenuItem::Resume => "Resume".to_string(),
+            MenuItem::Restart => "Restart mission".to_string(),
+            MenuItem::MainMenu => "Main menu".to_string(),
+            MenuItem::Quit => "Quit".to_string()
+        }
+    }
+}
+
+// a vertical list picked with the arrow keys or the mouse, drawn from `top` down
+pub struct Menu {
+    items: Vec<MenuItem>,
+    selected: usize,
+    top: f64
+}
+
+impl Menu {
+
+    pub fn new(items: Vec<MenuItem>, top: f64) -> Self {
+        Self {
+            items,
+            selected: 0,
+            top
+        }
+    }
+
+    pub fn select(&mut self, item: MenuItem) {
+        if let Some(index) = self.items.iter().position(|other| *other == item) {
+            self.selected = index;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len();
+    }
+
+    pub fn get_selected(&self) -> MenuItem {
+        self.items[self.selected]
+    }
+
+    // selects and returns the item under the cursor, if any
+    pub fn select_at(&mut self, cursor: &vec2d::Vec2D) -> Option<MenuItem> {
+        let index = ((cursor.y - self.top) / ITEM_HEIGHT).floor();
+        if cursor.x < MENU_LEFT || index < 0.0 || index >= self.items.len() as f64 {
+            return None;
+        }
+        self.selected = index as usize;
+        Some(self.get_selected())
+    }
+
+    pub fn draw(&self, font: &mut Glyphs, c: &Context, gl: &mut G2d) {
+        for (index, item) in self.items.iter().enumerate() {
+            let is_selected = index == self.selected;
+            let label = if is_selected { format!("> {}", item.get_label()) } else { item.get_label() };
+            let color = if is_selected { SELECTED_COLOR } else { TEXT_COLOR };
+            let baseline = self.top + ITEM_HEIGHT * (index as f64 + 0.75);
+            text(color, ITEM_FONT_SIZE, &label, font, c.transform.trans(MENU_LEFT, baseline), gl).unwrap();
+        }
+        let hint_baseline = self.top + ITEM_HEIGHT * (self.items.len() as f64 + 1.0);
+        text(HINT_COLOR, 20, "[Up/Down] choose  [Enter] confirm  [Esc] back", font, c.transform.trans(MENU_LEFT, hint_baseline), gl).unwrap();
+    }
+}
+
+pub fn draw_title(title: &str, font: &mut Glyphs, c: &Context, gl: &mut G2d) {
+    text(TEXT_COLOR, TITLE_FONT_SIZE, title, font, c.transform.trans(MENU_LEFT, TITLE_TOP), gl).unwrap();
+}
@@ -0,0 +1,103 @@
+use std::collections::VecDeque;
+use war_fog_experiment::events::GameEvent;
+use war_fog_experiment::game::{UnitId, COMMANDER};
+use war_fog_experiment::snapshot::{Outcome, Side};
+
+const MESSAGE_LIFETIME: f64 = 20.0;
+const FADE_DURATION: f64 = 5.0;
+const MAX_MESSAGES: usize = 8;
+
+pub struct LogEntry {
+    pub text: String,
+    pub time: f64
+}
+
+pub struct MessageLog {
+    entries: VecDeque<LogEntry>
+}
+
+impl MessageLog {
+
+    pub fn new() -> Self {
+        Self {
+            entries: VecDeque::new()
+        }
+    }
+
+    pub fn push(&mut self, text: String, time: f64) {
+        self.entries.push_back(LogEntry { text, time });
+        while self.entries.len() > MAX_MESSAGES {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn record_events(&mut self, events: &[GameEvent], time: f64) {
+        for event in events {
+            if let Some(text) = describe_event(event, time) {
+                self.push(text, time);
+            }
+        }
+    }
+
+    // entries with their opacity, fading out over the last seconds of their lifetime
+    pub fn get_visible_entries(&self, time: f64) -> impl Iterator<Item = (&LogEntry, f64)> {
+        self.entries.iter()
+            .map(move |entry| (entry, num::clamp((entry.time + MESSAGE_LIFETIME - time) / FADE_DURATION, 0.0, 1.0)))
+            .filter(|(_, opacity)| *opacity > 0.0)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+pub fn format_game_time(time: f64) -> String {
+    let seconds = time.max(0.0) as u64;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn describe_event(event: &GameEvent, time: f64) -> Option<String> {
+    let text = match *event {
+        GameEvent::EnemySighted { position } => format!("Contact! Enemy sighted at ({:.0}, {:.0})", position.x, position.y),
+        GameEvent::ScoutReturned { has_report } => {
+            if has_report { return None; }
+            "Scout returned: nothing to report".to_string()
+        },
+        GameEvent::ReportDelivered { enemy_position, observed_at } => format!("Report: enemy seen at ({:.0}, {:.0}) {:.0}s ago", enemy_position.x, enemy_position.y, time - observed_at),
+        GameEvent::CourierDispatched { sender, recipient } => {
+            if sender == UnitId::Unit(COMMANDER) {
+                format!("Messenger sent with orders for {}", describe_unit(recipient))
+            } else {
+                format!("{} sent a courier with a report", describe_unit(sender))
+            }
+        },
+        GameEvent::CourierIntercepted { sender } => format!("A courier from {} was intercepted!", describe_unit(sender)),
+        GameEvent::CourierLost { sender } => format!("A courier from {} never arrived", describe_unit(sender)),
+        GameEvent::OrderDelivered { unit, latency } => format!("{} received its orders after {:.0}s", describe_unit(unit), latency),
+        GameEvent::OrderIgnored { unit } => format!("{} ignored its orders", describe_unit(unit)),
+        GameEvent::FightStarted { unit } => format!("{} is engaged!", describe_unit(unit)),
+        GameEvent::Ambushed { side: Side::Friendly, unit } => format!("{} was ambushed!", describe_side_unit(unit)),
+        GameEvent::Ambushed { side: Side::Enemy, .. } => "The enemy was caught by surprise!".to_string(),
+        GameEvent::UnitRouted { side: Side::Friendly, unit } => format!("{} is routing!", describe_side_unit(unit)),
+        GameEvent::UnitRouted { side: Side::Enemy, .. } => "The enemy is routing!".to_string(),
+        GameEvent::UnitRallied { side: Side::Friendly, unit } => format!("{} has rallied", describe_side_unit(unit)),
+        GameEvent::UnitRallied { side: Side::Enemy, .. } => "The enemy has rallied".to_string(),
+        GameEvent::UnitDied { side: Side::Friendly, unit } => format!("{} has been wiped out", describe_side_unit(unit)),
+        GameEvent::UnitDied { side: Side::Enemy, .. } => "The enemy has been destroyed".to_string(),
+        GameEvent::GameOver { outcome: Outcome::Victory } => "Victory".to_string(),
+        GameEvent::GameOver { outcome: Outcome::Defeat } => "Defeat".to_string()
+    };
+    Some(text)
+}
+
+pub fn describe_unit(unit: UnitId) -> String {
+    match unit {
+        UnitId::Unit(COMMANDER) => "The commander".to_string(),
+        UnitId::Unit(index) => format!("Unit {index}"),
+        UnitId::Scout => "The scout".to_string()
+    }
+}
+
+fn describe_side_unit(unit: Option<UnitId>) -> String {
+    unit.map(describe_unit).unwrap_or_else(|| "A unit".to_string())
+}
\ No newline at end of file
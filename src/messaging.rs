@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use crate::character;
+use crate::config;
+use crate::vec2d;
+
+pub const COURIER_SPEED: f64 = 100.0;
+const COURIER_SOLDIERS: u32 = 1;
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub enum MessageContent {
+    EnemyReport { enemy_position: vec2d::Vec2D, observed_at: f64 },
+    MoveOrder { target_position: vec2d::Vec2D }
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub content: MessageContent,
+    pub sender: usize,
+    pub recipient: usize,
+    pub sent_at: f64
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub enum CourierStatus {
+    EnRoute,
+    Delivered,
+    Intercepted,
+    Lost
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Courier {
+    character: character::Character,
+    message: Message,
+    status: CourierStatus
+}
+
+impl Courier {
+
+    pub fn new(message: Message, start_position: &vec2d::Vec2D, config: &config::GameConfig) -> Self {
+        let mut courier = Self {
+            character: character::Character::new(),
+            message,
+            status: CourierStatus::EnRoute
+        };
+
+        courier.character.configure(config);
+        courier.character.set_max_speed(config.courier_speed);
+        courier.character.set_soldiers(COURIER_SOLDIERS);
+        courier.character.set_position(start_position);
+
+        courier
+    }
+
+    // the recipient keeps moving, so the courier heads for wherever it is now
+    pub fn update(&mut self, dt: &f64, recipient_position: &vec2d::Vec2D) {
+        self.character.set_target(recipient_position);
+        self.character.update(dt);
+    }
+
+    pub fn has_reached(&self, position: &vec2d::Vec2D) -> bool {
+        self.character.is_touching(position)
+    }
+
+    pub fn get_character(&self) -> &character::Character {
+        &self.character
+    }
+
+    pub fn get_position(&self) -> &vec2d::Vec2D {
+        self.character.get_position()
+    }
+
+    pub fn get_message(&self) -> &Message {
+        &self.message
+    }
+
+    pub fn get_status(&self) -> &CourierStatus {
+        &self.status
+    }
+
+    pub fn set_status(&mut self, status: CourierStatus) {
+        self.status = status;
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.character.set_visible(visible);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.character.is_visible()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use piston_window::*;
+use crate::camera::Camera;
+use war_fog_experiment::exploration::{ExploredArea, CELL_SIZE};
+use war_fog_experiment::snapshot::{Side, WorldSnapshot};
+use war_fog_experiment::vec2d;
+
+const MINIMAP_WIDTH: f64 = 240.0;
+const MINIMAP_MARGIN: f64 = 20.0;
+const MARKER_SIZE: f64 = 4.0;
+const FOG_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.6];
+const BORDER_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+const FRIENDLY_COLOR: [f32; 4] = [0.0, 0.4, 1.0, 1.0];
+const ENEMY_COLOR: [f32; 4] = [0.9, 0.0, 0.0, 1.0];
+const CAMERA_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+pub struct Minimap {
+    map_size: vec2d::Vec2D
+}
+
+impl Minimap {
+
+    pub fn new(map_size: vec2d::Vec2D) -> Self {
+        Self {
+            map_size
+        }
+    }
+
+    // top right corner of the window, keeping the map's aspect ratio
+    fn get_bounds(&self, window_size: &vec2d::Vec2D) -> [f64; 4] {
+        let height = MINIMAP_WIDTH * self.map_size.y / self.map_size.x;
+        [window_size.x - MINIMAP_WIDTH - MINIMAP_MARGIN, MINIMAP_MARGIN, MINIMAP_WIDTH, height]
+    }
+
+    fn get_scale(&self) -> f64 {
+        MINIMAP_WIDTH / self.map_size.x
+    }
+
+    fn to_minimap_position(&self, world_position: &vec2d::Vec2D, window_size: &vec2d::Vec2D) -> vec2d::Vec2D {
+        let bounds = self.get_bounds(window_size);
+        vec2d::Vec2D {
+            x: bounds[0] + (world_position.x + self.map_size.x / 2.0) * self.get_scale(),
+            y: bounds[1] + (world_position.y + self.map_size.y / 2.0) * self.get_scale()
+        }
+    }
+
+    pub fn to_world_position(&self, screen_position: &vec2d::Vec2D, window_size: &vec2d::Vec2D) -> Option<vec2d::Vec2D> {
+        let bounds = self.get_bounds(window_size);
+        let is_inside = screen_position.x >= bounds[0] && screen_position.x <= bounds[0] + bounds[2]
+            && screen_position.y >= bounds[1] && screen_position.y <= bounds[1] + bounds[3];
+        if !is_inside {
+            return None;
+        }
+        Some(vec2d::Vec2D {
+            x: (screen_position.x - bounds[0]) / self.get_scale() - self.map_size.x / 2.0,
+            y: (screen_position.y - bounds[1]) / self.get_scale() - self.map_size.y / 2.0
+        })
+    }
+
+    pub fn draw(&self, snapshot: &WorldSnapshot, map_texture: &G2dTexture, camera: &Camera, c: &Context, gl: &mut G2d) {
+        let window_size = camera.get_viewport_size();
+        let bounds = self.get_bounds(&window_size);
+
+        let map_scale = bounds[2] / map_texture.get_width() as f64;
+        image(map_texture, c.transform.trans(bounds[0], bounds[1]).scale(map_scale, map_scale), gl);
+
+        self.draw_fog(&snapshot.explored_area, &window_size, c, gl);
+
+        for unit in snapshot.units.iter() {
+            let color = if unit.side == Side::Friendly { FRIENDLY_COLOR } else { ENEMY_COLOR };
+            self.draw_marker(&unit.position, color, &window_size, c, gl);
+        }
+
+        if snapshot.get_units_of(Side::Enemy).next().is_none() {
+            if let Some(enemy_intel) = snapshot.enemy_intel {
+                let marker = self.to_minimap_position(&enemy_intel.position, &window_size);
+                Rectangle::new_border(ENEMY_COLOR, 1.0)
+                    .draw([marker.x - MARKER_SIZE, marker.y - MARKER_SIZE, MARKER_SIZE * 2.0, MARKER_SIZE * 2.0], &DrawState::default(), c.transform, gl);
+            }
+        }
+
+        let camera_top_left = self.to_minimap_position(&camera.to_world_position(&vec2d::new()), &window_size);
+        let camera_size = [window_size.x / camera.get_zoom() * self.get_scale(), window_size.y / camera.get_zoom() * self.get_scale()];
+        Rectangle::new_border(CAMERA_COLOR, 1.0)
+            .draw([camera_top_left.x, camera_top_left.y, camera_size[0], camera_size[1]], &DrawState::default(), c.transform, gl);
+
+        Rectangle::new_border(BORDER_COLOR, 1.0).draw(bounds, &DrawState::default(), c.transform, gl);
+    }
+
+    // one rectangle per run of unexplored cells in a row rather than one per cell
+    fn draw_fog(&self, explored_area: &ExploredArea, window_size: &vec2d::Vec2D, c: &Context, gl: &mut G2d) {
+        let mut cell_y = -self.map_size.y / 2.0;
+        while cell_y < self.map_size.y / 2.0 {
+            let mut span_start = None;
+            let mut cell_x = -self.map_size.x / 2.0;
+            while cell_x < self.map_size.x / 2.0 {
+                let cell_centre = vec2d::Vec2D{ x: cell_x + CELL_SIZE / 2.0, y: cell_y + CELL_SIZE / 2.0 };
+                if explored_area.is_explored(&cell_centre) {
+                    if let Some(start_x) = span_start.take() {
+                        self.draw_fog_span(&vec2d::Vec2D{ x: start_x, y: cell_y }, cell_x, window_size, c, gl);
+                    }
+                } else if span_start.is_none() {
+                    span_start = Some(cell_x);
+                }
+                cell_x += CELL_SIZE;
+            }
+            if let Some(start_x) = span_start {
+                self.draw_fog_span(&vec2d::Vec2D{ x: start_x, y: cell_y }, cell_x, window_size, c, gl);
+            }
+            cell_y += CELL_SIZE;
+        }
+    }
+
+    fn draw_fog_span(&self, start: &vec2d::Vec2D, end_x: f64, window_size: &vec2d::Vec2D, c: &Context, gl: &mut G2d) {
+        let corner = self.to_minimap_position(start, window_size);
+        rectangle(FOG_COLOR, [corner.x, corner.y, (end_x - start.x) * self.get_scale(), CELL_SIZE * self.get_scale()], c.transform, gl);
+    }
+
+    fn draw_marker(&self, world_position: &vec2d::Vec2D, color: [f32; 4], window_size: &vec2d::Vec2D, c: &Context, gl: &mut G2d) {
+        let marker = self.to_minimap_position(world_position, window_size);
+        rectangle(color, [marker.x - MARKER_SIZE / 2.0, marker.y - MARKER_SIZE / 2.0, MARKER_SIZE, MARKER_SIZE], c.transform, gl);
+    }
+}
\ No newline at end of file
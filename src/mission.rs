@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use crate::snapshot::Outcome;
+use crate::vec2d;
+
+// destroying the enemy always wins and losing the commander always loses, whatever the objective
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Objective {
+    DestroyEnemy,
+    // the enemy holds its camp until it sees someone; the commander must know where it is in time
+    LocateEnemyCamp { time_limit: f64 },
+    // the commander must get there without the enemy ever seeing a friendly unit
+    ReachExtraction { position: vec2d::Vec2D, radius: f64 },
+    // the convoy unit must reach its destination alive
+    EscortConvoy { convoy: usize, destination: vec2d::Vec2D, radius: f64 },
+    // the enemy marches on the position and must not hold it while no friendly unit does
+    DefendPosition { position: vec2d::Vec2D, radius: f64, duration: f64 }
+}
+
+impl Objective {
+
+    pub fn get_description(&self) -> String {
+        match self {
+            Objective::DestroyEnemy => "Find and destroy the enemy force".to_string(),
+            Objective::LocateEnemyCamp { time_limit } => format!("Get the position of the enemy camp back to the commander within {}", format_duration(*time_limit)),
+            Objective::ReachExtraction { .. } => "Bring the commander to the extraction point without being seen by the enemy".to_string(),
+            Objective::EscortConvoy { convoy, .. } => format!("Escort unit {convoy} to its destination"),
+            Objective::DefendPosition { duration, .. } => format!("Hold the marked position for {}", format_duration(*duration))
+        }
+    }
+
+    // where the objective is shown on the map, an enemy camp has to be found first
+    pub fn get_area(&self) -> Option<(vec2d::Vec2D, f64)> {
+        match self {
+            Objective::DestroyEnemy | Objective::LocateEnemyCamp { .. } => None,
+            Objective::ReachExtraction { position, radius } => Some((*position, *radius)),
+            Objective::EscortConvoy { destination, radius, .. } => Some((*destination, *radius)),
+            Objective::DefendPosition { position, radius, .. } => Some((*position, *radius))
+        }
+    }
+
+    pub fn get_time_limit(&self) -> Option<f64> {
+        match self {
+            Objective::LocateEnemyCamp { time_limit } => Some(*time_limit),
+            Objective::DefendPosition { duration, .. } => Some(*duration),
+            _ => None
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum MissionEnd {
+    EnemyDestroyed,
+    CommanderKilled,
+    CampLocated,
+    CampNotFound,
+    Extracted,
+    Detected,
+    ConvoyArrived,
+    ConvoyLost,
+    PositionHeld,
+    PositionLost
+}
+
+impl MissionEnd {
+
+    pub fn get_outcome(&self) -> Outcome {
+        match self {
+            MissionEnd::EnemyDestroyed | MissionEnd::CampLocated | MissionEnd::Extracted | MissionEnd::ConvoyArrived | MissionEnd::PositionHeld => Outcome::Victory,
+            MissionEnd::CommanderKilled | MissionEnd::CampNotFound | MissionEnd::Detected | MissionEnd::ConvoyLost | MissionEnd::PositionLost => Outcome::Defeat
+        }
+    }
+
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            MissionEnd::EnemyDestroyed => "The enemy force was destroyed",
+            MissionEnd::CommanderKilled => "The commander's column was wiped out",
+            MissionEnd::CampLocated => "The commander learned where the enemy camp is",
+            MissionEnd::CampNotFound => "The enemy camp was not found in time",
+            MissionEnd::Extracted => "The commander reached the extraction point unseen",
+            MissionEnd::Detected => "The enemy spotted our troops",
+            MissionEnd::ConvoyArrived => "The convoy reached its destination",
+            MissionEnd::ConvoyLost => "The convoy was wiped out",
+            MissionEnd::PositionHeld => "The position was held",
+            MissionEnd::PositionLost => "The enemy took the position"
+        }
+    }
+}
+
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
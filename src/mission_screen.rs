@@ -0,0 +1,54 @@
+use piston_window::*;
+use war_fog_experiment::after_action::AfterActionReport;
+use war_fog_experiment::scenario::Scenario;
+use war_fog_experiment::snapshot::{Outcome, WorldSnapshot};
+use crate::message_log;
+
+const SCREEN_MARGIN: f64 = 100.0;
+const BACKGROUND_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const HINT_COLOR: [f32; 4] = [0.7, 0.7, 0.7, 1.0];
+const OBJECTIVE_COLOR: [f32; 4] = [1.0, 0.8, 0.0, 1.0];
+
+pub fn draw_briefing(scenario: &Scenario, font: &mut Glyphs, c: &Context, gl: &mut G2d) {
+    clear(BACKGROUND_COLOR, gl);
+    let lines = [
+        (TEXT_COLOR, 48, format!("Mission : {}", scenario.name)),
+        (TEXT_COLOR, 28, scenario.description.to_string()),
+        (OBJECTIVE_COLOR, 28, format!("Objective : {}", scenario.objective.get_description())),
+        (HINT_COLOR, 20, "Destroying the enemy always wins, losing the commander's column always loses".to_string()),
+        (HINT_COLOR, 24, "Press any key to begin".to_string())
+    ];
+    draw_lines(&lines, font, c, gl);
+}
+
+pub fn draw_summary(snapshot: &WorldSnapshot, scenario: &Scenario, report: &AfterActionReport, font: &mut Glyphs, c: &Context, gl: &mut G2d) {
+    clear(BACKGROUND_COLOR, gl);
+    let title = if snapshot.outcome == Some(Outcome::Victory) { "Mission accomplished" } else { "Mission failed" };
+    let reason = snapshot.mission_end.map(|mission_end| mission_end.get_description()).unwrap_or_default();
+    let mean_intel_age = report.mean_intel_age.map(|age| format!("{age:.0}s")).unwrap_or("none".to_string());
+    let mean_order_latency = report.mean_order_latency.map(|latency| format!("{latency:.0}s")).unwrap_or("none".to_string());
+    let lines = [
+        (TEXT_COLOR, 64, title.to_string()),
+        (OBJECTIVE_COLOR, 32, reason.to_string()),
+        (TEXT_COLOR, 24, format!("Mission : {}", scenario.name)),
+        (TEXT_COLOR, 24, format!("Time played : {}", message_log::format_game_time(report.time))),
+        (TEXT_COLOR, 24, format!("Distance marched : {:.0}", report.distance_travelled)),
+        (TEXT_COLOR, 24, format!("Scout missions : {} sent, {} returned", report.scout_missions_sent, report.scout_missions_returned)),
+        (TEXT_COLOR, 24, format!("Orders given : {}, {} without any intel", report.orders_given, report.orders_without_intel)),
+        (TEXT_COLOR, 24, format!("Average intel age at orders : {mean_intel_age}")),
+        (TEXT_COLOR, 24, format!("Average order delay by messenger : {mean_order_latency}")),
+        (TEXT_COLOR, 24, format!("Men lost : {}", report.soldiers_lost)),
+        (TEXT_COLOR, 24, format!("Enemy casualties : {}", report.enemy_soldiers_killed))
+    ];
+    draw_lines(&lines, font, c, gl);
+}
+
+// each line sits below the previous one with a gap proportional to its size
+fn draw_lines(lines: &[([f32; 4], u32, String)], font: &mut Glyphs, c: &Context, gl: &mut G2d) {
+    let mut y = SCREEN_MARGIN;
+    for (color, size, line) in lines.iter() {
+        y += *size as f64 * 1.6;
+        text(*color, *size, line, font, c.transform.trans(SCREEN_MARGIN, y), gl).unwrap();
+    }
+}
@@ -0,0 +1,103 @@
+use piston_window::*;
+use war_fog_experiment::scout::ScoutStatus;
+use war_fog_experiment::snapshot::{Side, UnitKind, UnitView, WorldSnapshot};
+use war_fog_experiment::vec2d;
+use crate::camera::Camera;
+
+const FRIENDLY_VISION_COLOR: [f32; 4] = [0.2, 0.5, 1.0, 0.12];
+const ENEMY_VISION_COLOR: [f32; 4] = [1.0, 0.1, 0.1, 0.12];
+const FIGHT_RANGE_COLOR: [f32; 4] = [1.0, 0.5, 0.0, 0.8];
+const ROUTE_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.9];
+const ROUTE_POINT_SIZE: f64 = 8.0;
+const OBJECTIVE_COLOR: [f32; 4] = [1.0, 0.8, 0.0, 0.9];
+
+pub struct Overlays {
+    is_vision_shown: bool,
+    is_route_shown: bool
+}
+
+impl Overlays {
+
+    pub fn new() -> Self {
+        Self {
+            is_vision_shown: false,
+            is_route_shown: false
+        }
+    }
+
+    pub fn toggle_vision(&mut self) {
+        self.is_vision_shown = !self.is_vision_shown;
+    }
+
+    pub fn toggle_route(&mut self) {
+        self.is_route_shown = !self.is_route_shown;
+    }
+
+    // enemy units only show up in an omniscient snapshot, so their vision is limited to god mode
+    pub fn draw(&self, snapshot: &WorldSnapshot, camera: &Camera, c: &Context, gl: &mut G2d) {
+        // the objective stays on the map whatever is toggled
+        if let Some((position, radius)) = snapshot.objective.get_area() {
+            let objective_area = get_screen_square(&position, radius, camera);
+            rectangle([OBJECTIVE_COLOR[0], OBJECTIVE_COLOR[1], OBJECTIVE_COLOR[2], 0.15], objective_area, c.transform, gl);
+            Rectangle::new_border(OBJECTIVE_COLOR, 2.0).draw(objective_area, &DrawState::default(), c.transform, gl);
+        }
+
+        if self.is_vision_shown {
+            for unit in snapshot.units.iter().filter(|unit| has_vision(unit)) {
+                let color = if unit.side == Side::Friendly { FRIENDLY_VISION_COLOR } else { ENEMY_VISION_COLOR };
+                let vision_area = get_screen_square(&unit.position, unit.view_radius, camera);
+                rectangle(color, vision_area, c.transform, gl);
+                Rectangle::new_border([color[0], color[1], color[2], 0.6], 1.0).draw(vision_area, &DrawState::default(), c.transform, gl);
+
+                if unit.kind == UnitKind::Troops {
+                    let fight_range = get_screen_square(&unit.position, snapshot.fight_radius, camera);
+                    Rectangle::new_border(FIGHT_RANGE_COLOR, 0.5).draw(fight_range, &DrawState::default(), c.transform, gl);
+                }
+            }
+        }
+
+        if self.is_route_shown {
+            if let Some(route) = snapshot.scout_route {
+                let return_point = camera.to_screen_position(&route.return_position);
+                let scout = snapshot.get_units_of(Side::Friendly).find(|unit| unit.kind == UnitKind::Scout);
+                let mut route_points = Vec::new();
+                if let Some(scout) = scout {
+                    route_points.push(camera.to_screen_position(&scout.position));
+                }
+                if let Some(target_position) = route.target_position {
+                    let target_point = camera.to_screen_position(&target_position);
+                    draw_route_point(&target_point, c, gl);
+                    route_points.push(target_point);
+                }
+                route_points.push(return_point);
+                draw_route_point(&return_point, c, gl);
+
+                let route_line = Line::new(ROUTE_COLOR, 1.0);
+                for leg in route_points.windows(2) {
+                    route_line.draw([leg[0].x, leg[0].y, leg[1].x, leg[1].y], &DrawState::default(), c.transform, gl);
+                }
+            }
+        }
+    }
+}
+
+// an idle scout waits with the commander and sees nothing of its own
+fn has_vision(unit: &UnitView) -> bool {
+    match unit.kind {
+        UnitKind::Troops => true,
+        UnitKind::Scout => unit.scout_status != Some(ScoutStatus::Idle),
+        UnitKind::Courier => false
+    }
+}
+
+// ranges are squares, see collision::are_positions_in_range
+fn get_screen_square(center: &vec2d::Vec2D, radius: f64, camera: &Camera) -> [f64; 4] {
+    let top_left = camera.to_screen_position(&vec2d::Vec2D{ x: center.x - radius, y: center.y - radius });
+    let size = radius * 2.0 * camera.get_zoom();
+    [top_left.x, top_left.y, size, size]
+}
+
+fn draw_route_point(position: &vec2d::Vec2D, c: &Context, gl: &mut G2d) {
+    Rectangle::new_border(ROUTE_COLOR, 1.0)
+        .draw([position.x - ROUTE_POINT_SIZE / 2.0, position.y - ROUTE_POINT_SIZE / 2.0, ROUTE_POINT_SIZE, ROUTE_POINT_SIZE], &DrawState::default(), c.transform, gl);
+}
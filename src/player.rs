@@ -0,0 +1,58 @@
+use crate::game::{Game, UnitId};
+use crate::snapshot::WorldSnapshot;
+use crate::vec2d;
+
+const DECISION_INTERVAL: f64 = 1.0;
+
+// the orders a player can give, the same ones the mouse gives in the window
+pub trait PlayerCommands {
+    fn order_units(&mut self, units: &[UnitId], target_position: &vec2d::Vec2D);
+    fn set_scout_mission(&mut self, target_position: &vec2d::Vec2D);
+}
+
+impl PlayerCommands for Game {
+
+    fn order_units(&mut self, units: &[UnitId], target_position: &vec2d::Vec2D) {
+        Game::order_units(self, units, target_position);
+    }
+
+    fn set_scout_mission(&mut self, target_position: &vec2d::Vec2D) {
+        Game::set_scout_mission(self, target_position);
+    }
+}
+
+// plays the player's side from the same fogged snapshot a human sees
+pub trait PlayerController {
+    fn get_name(&self) -> String;
+    fn act(&mut self, snapshot: &WorldSnapshot, commands: &mut dyn PlayerCommands);
+}
+
+// lets a controller decide once per DECISION_INTERVAL of game time, as often as a human would reasonably react
+pub struct Bot<C: PlayerController> {
+    controller: C,
+    next_decision_time: f64
+}
+
+impl<C: PlayerController> Bot<C> {
+
+    pub fn new(controller: C) -> Self {
+        Self {
+            controller,
+            next_decision_time: 0.0
+        }
+    }
+
+    pub fn is_due(&self, time: f64) -> bool {
+        time >= self.next_decision_time
+    }
+
+    // the next decision is due an interval after this one, however late this one came
+    pub fn act(&mut self, time: f64, snapshot: &WorldSnapshot, commands: &mut dyn PlayerCommands) {
+        self.controller.act(snapshot, commands);
+        self.next_decision_time = time + DECISION_INTERVAL;
+    }
+
+    pub fn get_controller(&self) -> &C {
+        &self.controller
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use crate::config;
+use crate::events::GameEvent;
+use crate::game::{Game, UnitId};
+use crate::player::PlayerCommands;
+use crate::scenario;
+use crate::vec2d;
+
+// one player command, whatever number of units it went to
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplayOrder {
+    pub units: Vec<UnitId>,
+    pub target_position: vec2d::Vec2D
+}
+
+// orders are given just before the update they were issued ahead of
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub dt: f64,
+    pub orders: Vec<ReplayOrder>
+}
+
+// the simulation is deterministic, so the starting setup plus every step's dt and orders reproduce a game exactly
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    scenario: String,
+    seed: u64,
+    config: config::GameConfig,
+    steps: Vec<ReplayStep>
+}
+
+impl Replay {
+
+    pub fn new(scenario: &str, seed: u64, config: config::GameConfig) -> Self {
+        Self {
+            scenario: scenario.to_string(),
+            seed,
+            config,
+            steps: Vec::new()
+        }
+    }
+
+    // None if the scenario it was recorded on no longer exists
+    pub fn create_game(&self) -> Option<Game> {
+        scenario::find_scenario(&self.scenario).map(|scenario| Game::with_scenario(scenario, self.config.clone(), self.seed))
+    }
+
+    pub fn record_step(&mut self, dt: f64, orders: Vec<ReplayOrder>) {
+        self.steps.push(ReplayStep { dt, orders });
+    }
+
+    pub fn get_scenario(&self) -> &str {
+        &self.scenario
+    }
+}
+
+// passes orders on to the game and notes them down for the step being recorded
+pub struct RecordingCommands<'a> {
+    pub game: &'a mut Game,
+    pub orders: &'a mut Vec<ReplayOrder>
+}
+
+impl PlayerCommands for RecordingCommands<'_> {
+
+    fn order_units(&mut self, units: &[UnitId], target_position: &vec2d::Vec2D) {
+        self.game.order_units(units, target_position);
+        self.orders.push(ReplayOrder { units: units.to_vec(), target_position: *target_position });
+    }
+
+    fn set_scout_mission(&mut self, target_position: &vec2d::Vec2D) {
+        self.order_units(&[UnitId::Scout], target_position);
+    }
+}
+
+pub struct ReplayPlayer {
+    replay: Replay,
+    next_step: usize,
+    pending_time: f64
+}
+
+impl ReplayPlayer {
+
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next_step: 0,
+            pending_time: 0.0
+        }
+    }
+
+    // plays every recorded step that fits in the time elapsed so far, whatever the step sizes were when recording
+    pub fn advance(&mut self, game: &mut Game, dt: f64) -> Vec<GameEvent> {
+        self.pending_time += dt;
+        let mut events = Vec::new();
+        while let Some(step) = self.replay.steps.get(self.next_step) {
+            if step.dt > self.pending_time {
+                break;
+            }
+            self.pending_time -= step.dt;
+            for order in step.orders.iter() {
+                game.order_units(&order.units, &order.target_position);
+            }
+            events.extend(game.update(&step.dt));
+            self.next_step += 1;
+        }
+        events
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_step >= self.replay.steps.len()
+    }
+}
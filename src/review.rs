@@ -0,0 +1,159 @@
+use piston_window::*;
+use war_fog_experiment::after_action::{Timeline, TimelineSample};
+use war_fog_experiment::vec2d;
+use crate::message_log;
+
+// below the summary title
+const PANEL_TOP: f64 = 230.0;
+const PANEL_MARGIN: f64 = 60.0;
+const BAR_HEIGHT: f64 = 16.0;
+const BAR_GAP: f64 = 20.0;
+// room for the bar, the caption and the hint under the map
+const FOOTER_HEIGHT: f64 = 110.0;
+const MARKER_SIZE: f64 = 8.0;
+const STEP_SAMPLES: i64 = 5;
+const BAR_COLOR: [f32; 4] = [0.3, 0.3, 0.3, 1.0];
+const BAR_FILL_COLOR: [f32; 4] = [0.7, 0.7, 0.7, 1.0];
+const BORDER_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const FRIENDLY_COLOR: [f32; 4] = [0.0, 0.4, 1.0, 1.0];
+const ENEMY_COLOR: [f32; 4] = [0.9, 0.0, 0.0, 1.0];
+const ERROR_COLOR: [f32; 4] = [1.0, 0.8, 0.0, 1.0];
+
+// scrubs through the timeline on the game over screen, comparing where the enemy was with where the commander thought it was
+pub struct Review {
+    map_size: vec2d::Vec2D,
+    bottom: f64,
+    selected_sample: Option<usize>,
+    is_scrubbing: bool
+}
+
+impl Review {
+
+    // nothing is drawn below bottom, where the game over menu starts
+    pub fn new(map_size: vec2d::Vec2D, bottom: f64) -> Self {
+        Self {
+            map_size,
+            bottom,
+            selected_sample: None,
+            is_scrubbing: false
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.selected_sample = None;
+        self.is_scrubbing = false;
+    }
+
+    // right half of the window, keeping the map's aspect ratio and leaving room for the footer
+    fn get_panel_bounds(&self, window_size: &vec2d::Vec2D) -> [f64; 4] {
+        let max_height = (self.bottom.min(window_size.y) - PANEL_TOP - FOOTER_HEIGHT).max(0.0);
+        let width = (window_size.x / 2.0 - PANEL_MARGIN).min(max_height * self.map_size.x / self.map_size.y);
+        [window_size.x / 2.0, PANEL_TOP, width, width * self.map_size.y / self.map_size.x]
+    }
+
+    fn get_bar_bounds(&self, window_size: &vec2d::Vec2D) -> [f64; 4] {
+        let panel = self.get_panel_bounds(window_size);
+        [panel[0], panel[1] + panel[3] + BAR_GAP, panel[2], BAR_HEIGHT]
+    }
+
+    fn to_panel_position(&self, world_position: &vec2d::Vec2D, window_size: &vec2d::Vec2D) -> vec2d::Vec2D {
+        let panel = self.get_panel_bounds(window_size);
+        let scale = panel[2] / self.map_size.x;
+        vec2d::Vec2D {
+            x: panel[0] + (world_position.x + self.map_size.x / 2.0) * scale,
+            y: panel[1] + (world_position.y + self.map_size.y / 2.0) * scale
+        }
+    }
+
+    fn get_selected_index(&self, sample_count: usize) -> Option<usize> {
+        let last = sample_count.checked_sub(1)?;
+        Some(self.selected_sample.unwrap_or(last).min(last))
+    }
+
+    pub fn begin_scrub(&mut self, cursor: &vec2d::Vec2D, window_size: &vec2d::Vec2D, sample_count: usize) {
+        let bar = self.get_bar_bounds(window_size);
+        let is_on_bar = cursor.x >= bar[0] && cursor.x <= bar[0] + bar[2] && cursor.y >= bar[1] - BAR_HEIGHT && cursor.y <= bar[1] + bar[3] * 2.0;
+        if is_on_bar {
+            self.is_scrubbing = true;
+            self.scrub(cursor, window_size, sample_count);
+        }
+    }
+
+    pub fn scrub(&mut self, cursor: &vec2d::Vec2D, window_size: &vec2d::Vec2D, sample_count: usize) {
+        if !self.is_scrubbing || sample_count == 0 {
+            return;
+        }
+        let bar = self.get_bar_bounds(window_size);
+        let fraction = ((cursor.x - bar[0]) / bar[2]).clamp(0.0, 1.0);
+        self.selected_sample = Some((fraction * (sample_count - 1) as f64).round() as usize);
+    }
+
+    pub fn end_scrub(&mut self) {
+        self.is_scrubbing = false;
+    }
+
+    // a few seconds at a time, backwards for negative directions
+    pub fn step(&mut self, direction: i64, sample_count: usize) {
+        if let Some(index) = self.get_selected_index(sample_count) {
+            let index = (index as i64 + direction * STEP_SAMPLES).clamp(0, sample_count as i64 - 1);
+            self.selected_sample = Some(index as usize);
+        }
+    }
+
+    pub fn draw(&self, timeline: &Timeline, map_texture: &G2dTexture, window_size: &vec2d::Vec2D, font: &mut Glyphs, c: &Context, gl: &mut G2d) {
+        let samples = timeline.get_samples();
+        let Some(index) = self.get_selected_index(samples.len()) else {
+            return;
+        };
+        let sample = &samples[index];
+        let panel = self.get_panel_bounds(window_size);
+
+        let map_scale = panel[2] / map_texture.get_width() as f64;
+        image(map_texture, c.transform.trans(panel[0], panel[1]).scale(map_scale, map_scale), gl);
+        Rectangle::new_border(BORDER_COLOR, 1.0).draw(panel, &DrawState::default(), c.transform, gl);
+
+        for position in sample.friendly_positions.iter() {
+            let marker = self.to_panel_position(position, window_size);
+            rectangle(FRIENDLY_COLOR, get_marker_square(&marker), c.transform, gl);
+        }
+        if let Some(enemy_position) = sample.enemy_position {
+            let marker = self.to_panel_position(&enemy_position, window_size);
+            rectangle(ENEMY_COLOR, get_marker_square(&marker), c.transform, gl);
+            if let Some(believed_position) = sample.believed_enemy_position {
+                let believed_marker = self.to_panel_position(&believed_position, window_size);
+                Line::new(ERROR_COLOR, 1.0).draw([believed_marker.x, believed_marker.y, marker.x, marker.y], &DrawState::default(), c.transform, gl);
+            }
+        }
+        if let Some(believed_position) = sample.believed_enemy_position {
+            let marker = self.to_panel_position(&believed_position, window_size);
+            Rectangle::new_border(ENEMY_COLOR, 2.0).draw(get_marker_square(&marker), &DrawState::default(), c.transform, gl);
+        }
+
+        let bar = self.get_bar_bounds(window_size);
+        let fraction = if samples.len() > 1 { index as f64 / (samples.len() - 1) as f64 } else { 1.0 };
+        rectangle(BAR_COLOR, bar, c.transform, gl);
+        rectangle(BAR_FILL_COLOR, [bar[0], bar[1], bar[2] * fraction, bar[3]], c.transform, gl);
+
+        let caption_transform = c.transform.trans(bar[0], bar[1] + bar[3] + BAR_GAP + 20.0);
+        text(TEXT_COLOR, 20, &describe_sample(sample), font, caption_transform, gl).unwrap();
+        let hint_transform = c.transform.trans(bar[0], bar[1] + bar[3] + BAR_GAP + 48.0);
+        text(TEXT_COLOR, 16, "Drag the bar or pan left and right to replay the enemy's true and believed positions", font, hint_transform, gl).unwrap();
+    }
+}
+
+fn describe_sample(sample: &TimelineSample) -> String {
+    let timestamp = message_log::format_game_time(sample.time);
+    match (sample.enemy_position, sample.believed_enemy_position, sample.believed_at) {
+        (Some(enemy_position), Some(believed_position), Some(believed_at)) => {
+            let error = ((enemy_position.x - believed_position.x).powi(2) + (enemy_position.y - believed_position.y).powi(2)).sqrt();
+            format!("[{timestamp}] Intel {:.0}s old, {error:.0} off the enemy's true position", sample.time - believed_at)
+        },
+        (None, _, _) => format!("[{timestamp}] The enemy has been destroyed"),
+        _ => format!("[{timestamp}] No intel on the enemy yet")
+    }
+}
+
+fn get_marker_square(center: &vec2d::Vec2D) -> [f64; 4] {
+    [center.x - MARKER_SIZE / 2.0, center.y - MARKER_SIZE / 2.0, MARKER_SIZE, MARKER_SIZE]
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::game;
+use crate::replay;
+
+const SAVE_FORMAT_VERSION: u32 = 9;
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Format(serde_json::Error),
+    VersionMismatch { found: u32, expected: u32 }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "could not access save file: {error}"),
+            SaveError::Format(error) => write!(f, "save file is corrupted: {error}"),
+            SaveError::VersionMismatch { found, expected } => write!(f, "save file has format version {found}, this build reads version {expected}")
+        }
+    }
+}
+
+impl From<std::io::Error> for SaveError {
+    fn from(error: std::io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(error: serde_json::Error) -> Self {
+        SaveError::Format(error)
+    }
+}
+
+#[derive(Serialize)]
+struct SaveFileWriter<'a> {
+    version: u32,
+    game: &'a game::Game
+}
+
+// read on its own first so an old file is reported as such rather than as corrupted
+#[derive(Deserialize)]
+struct SaveFileHeader {
+    version: u32
+}
+
+#[derive(Deserialize)]
+struct SaveFile {
+    game: game::Game
+}
+
+#[derive(Serialize)]
+struct ReplayFileWriter<'a> {
+    version: u32,
+    replay: &'a replay::Replay
+}
+
+#[derive(Deserialize)]
+struct ReplayFile {
+    replay: replay::Replay
+}
+
+pub fn save_game(game: &game::Game, path: &Path) -> Result<(), SaveError> {
+    let save_file = SaveFileWriter { version: SAVE_FORMAT_VERSION, game };
+    fs::write(path, serde_json::to_string(&save_file)?)?;
+    Ok(())
+}
+
+pub fn load_game(path: &Path) -> Result<game::Game, SaveError> {
+    let contents = fs::read_to_string(path)?;
+
+    let header: SaveFileHeader = serde_json::from_str(&contents)?;
+    if header.version != SAVE_FORMAT_VERSION {
+        return Err(SaveError::VersionMismatch { found: header.version, expected: SAVE_FORMAT_VERSION });
+    }
+
+    let save_file: SaveFile = serde_json::from_str(&contents)?;
+    Ok(save_file.game)
+}
+
+// replays only hold up while the simulation they were recorded with is unchanged, so they share the save format version
+pub fn save_replay(replay: &replay::Replay, path: &Path) -> Result<(), SaveError> {
+    let replay_file = ReplayFileWriter { version: SAVE_FORMAT_VERSION, replay };
+    fs::write(path, serde_json::to_string(&replay_file)?)?;
+    Ok(())
+}
+
+pub fn load_replay(path: &Path) -> Result<replay::Replay, SaveError> {
+    let contents = fs::read_to_string(path)?;
+
+    let header: SaveFileHeader = serde_json::from_str(&contents)?;
+    if header.version != SAVE_FORMAT_VERSION {
+        return Err(SaveError::VersionMismatch { found: header.version, expected: SAVE_FORMAT_VERSION });
+    }
+
+    let replay_file: ReplayFile = serde_json::from_str(&contents)?;
+    Ok(replay_file.replay)
+}
\ No newline at end of file
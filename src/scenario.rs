@@ -0,0 +1,88 @@
+use crate::mission::Objective;
+use crate::vec2d;
+
+pub const DEFAULT_SCENARIO: &str = "skirmish";
+
+// a starting layout: friendly units as (x, y, soldiers) with the commander first, the enemy spawns at random within spawn_range of the centre
+pub struct Scenario {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub friendly_units: &'static [(f64, f64, u32)],
+    pub enemy_soldiers: u32,
+    pub enemy_spawn_range: f64,
+    pub objective: Objective
+}
+
+pub const SCENARIOS: [Scenario; 7] = [
+    Scenario {
+        name: "skirmish",
+        description: "The commander and two detachments against an enemy force somewhere nearby",
+        friendly_units: &[(0.0, 0.0, 100), (-150.0, 100.0, 50), (150.0, 100.0, 50)],
+        enemy_soldiers: 100,
+        enemy_spawn_range: 500.0,
+        objective: Objective::DestroyEnemy
+    },
+    Scenario {
+        name: "lone-column",
+        description: "A single column and its scout searching a wide area",
+        friendly_units: &[(0.0, 0.0, 100)],
+        enemy_soldiers: 100,
+        enemy_spawn_range: 1000.0,
+        objective: Objective::DestroyEnemy
+    },
+    Scenario {
+        name: "outnumbered",
+        description: "Three units facing an enemy twice the size of the commander's column",
+        friendly_units: &[(0.0, 0.0, 100), (-150.0, 100.0, 50), (150.0, 100.0, 50)],
+        enemy_soldiers: 200,
+        enemy_spawn_range: 800.0,
+        objective: Objective::DestroyEnemy
+    },
+    Scenario {
+        name: "reconnaissance",
+        description: "The enemy has made camp somewhere in the area. Find it before it moves on",
+        friendly_units: &[(0.0, 0.0, 100)],
+        enemy_soldiers: 150,
+        enemy_spawn_range: 900.0,
+        objective: Objective::LocateEnemyCamp { time_limit: 300.0 }
+    },
+    Scenario {
+        name: "extraction",
+        description: "The column is behind enemy lines and must slip away to the north-east",
+        friendly_units: &[(-600.0, 400.0, 60)],
+        enemy_soldiers: 150,
+        enemy_spawn_range: 500.0,
+        objective: Objective::ReachExtraction { position: vec2d::Vec2D{ x: 700.0, y: -500.0 }, radius: 100.0 }
+    },
+    Scenario {
+        name: "convoy",
+        description: "A supply convoy has to cross the area while an enemy force roams nearby",
+        friendly_units: &[(-700.0, 0.0, 100), (-750.0, 50.0, 20)],
+        enemy_soldiers: 100,
+        enemy_spawn_range: 600.0,
+        objective: Objective::EscortConvoy { convoy: 1, destination: vec2d::Vec2D{ x: 700.0, y: 0.0 }, radius: 80.0 }
+    },
+    Scenario {
+        name: "hold-the-ford",
+        description: "The enemy is marching on the ford. Keep it out until reinforcements arrive",
+        friendly_units: &[(0.0, 50.0, 100), (-150.0, 100.0, 50), (150.0, 100.0, 50)],
+        enemy_soldiers: 220,
+        enemy_spawn_range: 900.0,
+        objective: Objective::DefendPosition { position: vec2d::Vec2D{ x: 0.0, y: 0.0 }, radius: 120.0, duration: 240.0 }
+    }
+];
+
+pub fn find_scenario(name: &str) -> Option<&'static Scenario> {
+    SCENARIOS.iter().find(|scenario| scenario.name == name)
+}
+
+pub fn get_default_scenario() -> &'static Scenario {
+    find_scenario(DEFAULT_SCENARIO).unwrap()
+}
+
+impl Scenario {
+
+    pub fn get_friendly_positions(&self) -> impl Iterator<Item = (vec2d::Vec2D, u32)> + '_ {
+        self.friendly_units.iter().map(|(x, y, soldiers)| (vec2d::Vec2D{ x: *x, y: *y }, *soldiers))
+    }
+}
@@ -1,22 +1,35 @@
+use serde::{Deserialize, Serialize};
 use crate::character;
+use crate::config;
+use crate::exploration;
 use crate::vec2d;
 
-const SCOUT_SPEED: f64 = 75.0;
+pub const SCOUT_SPEED: f64 = 75.0;
+const SCOUT_SOLDIERS: u32 = 1;
 
-#[derive(PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum ScoutStatus {
     Idle,
     GoingToTarget,
     GoingToPlayer
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Scout {
     character: character::Character,
     player_position: vec2d::Vec2D,
     has_enemy_position_to_deliver: bool,
+    // what the scout has seen on its mission, only known to the player once it is back
+    explored_area: exploration::ExploredArea,
     status: ScoutStatus
 }
 
+impl Default for Scout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Scout {
 
     pub fn new() -> Self {
@@ -24,18 +37,29 @@ impl Scout {
             character: character::Character::new(),
             player_position: vec2d::new(),
             has_enemy_position_to_deliver: false,
+            explored_area: exploration::ExploredArea::new(),
             status: ScoutStatus::Idle
         };
 
         new_scout.character.set_max_speed(SCOUT_SPEED);
+        new_scout.character.set_soldiers(SCOUT_SOLDIERS);
 
         new_scout
     
     }
 
+    pub fn configure(&mut self, config: &config::GameConfig) {
+        self.character.configure(config);
+        self.character.set_max_speed(config.scout_speed);
+    }
+
     pub fn update(&mut self, dt: &f64){
 
-        self.character.update(&dt);
+        self.character.update(dt);
+
+        if self.status != ScoutStatus::Idle {
+            self.explored_area.reveal(self.character.get_position(), self.character.get_view_radius());
+        }
 
         if self.character.has_reached_target(){
 
@@ -62,8 +86,16 @@ impl Scout {
         self.character.set_target(target_position);
     }
 
+    pub fn get_return_position(&self) -> &vec2d::Vec2D {
+        &self.player_position
+    }
+
+    pub fn get_character(&self) -> &character::Character {
+        &self.character
+    }
+
     pub fn get_position(&self) -> &vec2d::Vec2D {
-        &self.character.get_position()
+        self.character.get_position()
     }
 
     pub fn set_position(&mut self, position: &vec2d::Vec2D){
@@ -83,8 +115,12 @@ impl Scout {
         self.character.is_visible()
     }
 
-    pub fn discover_enemy(&mut self, enemy_position: &vec2d::Vec2D){
-        self.character.discover_enemy(enemy_position);
+    pub fn get_view_radius(&self) -> f64 {
+        self.character.get_view_radius()
+    }
+
+    pub fn discover_enemy(&mut self, enemy_position: &vec2d::Vec2D, time: f64){
+        self.character.discover_enemy(enemy_position, time);
         self.has_enemy_position_to_deliver = true;
         self.character.set_target(&self.player_position);
     }
@@ -95,7 +131,15 @@ impl Scout {
 
     pub fn deliver_enemy_position(&mut self) -> &vec2d::Vec2D {
         self.has_enemy_position_to_deliver = false;
-        &self.character.get_known_enemy_position()
+        self.character.get_known_enemy_position()
+    }
+
+    pub fn take_explored_area(&mut self) -> exploration::ExploredArea {
+        std::mem::take(&mut self.explored_area)
+    }
+
+    pub fn get_known_enemy_time(&self) -> f64 {
+        self.character.get_known_enemy_time()
     }
 
     pub fn get_status(&self) -> &ScoutStatus {
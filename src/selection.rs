@@ -0,0 +1,78 @@
+use war_fog_experiment::game::UnitId;
+use war_fog_experiment::vec2d;
+
+// in screen pixels, so a click stays a click whatever the zoom
+const CLICK_DRAG_THRESHOLD: f64 = 5.0;
+
+pub struct SelectableUnit {
+    pub id: UnitId,
+    pub position: vec2d::Vec2D,
+    pub radius: f64
+}
+
+pub struct Selection {
+    units: Vec<UnitId>,
+    drag_start: Option<vec2d::Vec2D>
+}
+
+impl Selection {
+
+    pub fn new() -> Self {
+        Self {
+            units: Vec::new(),
+            drag_start: None
+        }
+    }
+
+    pub fn begin_drag(&mut self, position: &vec2d::Vec2D) {
+        self.drag_start = Some(*position);
+    }
+
+    pub fn get_drag_start(&self) -> Option<&vec2d::Vec2D> {
+        self.drag_start.as_ref()
+    }
+
+    pub fn is_box_drag(&self, position: &vec2d::Vec2D, zoom: f64) -> bool {
+        match self.drag_start {
+            Some(start) => is_drag(&start, position, zoom),
+            None => false
+        }
+    }
+
+    // a short drag is a click and picks the unit under the cursor, a longer one picks every unit in the box
+    pub fn end_drag(&mut self, position: &vec2d::Vec2D, zoom: f64, selectable_units: &[SelectableUnit]) {
+        let start = match self.drag_start.take() {
+            Some(start) => start,
+            None => return
+        };
+
+        self.units.clear();
+
+        if is_drag(&start, position, zoom) {
+            let min_x = start.x.min(position.x);
+            let max_x = start.x.max(position.x);
+            let min_y = start.y.min(position.y);
+            let max_y = start.y.max(position.y);
+            for unit in selectable_units {
+                if unit.position.x >= min_x && unit.position.x <= max_x && unit.position.y >= min_y && unit.position.y <= max_y {
+                    self.units.push(unit.id);
+                }
+            }
+        }
+        else if let Some(unit) = selectable_units.iter().find(|unit| (unit.position.x - position.x).abs() < unit.radius && (unit.position.y - position.y).abs() < unit.radius) {
+            self.units.push(unit.id);
+        }
+    }
+
+    pub fn is_selected(&self, unit: UnitId) -> bool {
+        self.units.contains(&unit)
+    }
+
+    pub fn get_units(&self) -> &Vec<UnitId> {
+        &self.units
+    }
+}
+
+fn is_drag(start: &vec2d::Vec2D, end: &vec2d::Vec2D, zoom: f64) -> bool {
+    (end.x - start.x).abs() * zoom > CLICK_DRAG_THRESHOLD || (end.y - start.y).abs() * zoom > CLICK_DRAG_THRESHOLD
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use serde::Serialize;
+use crate::config;
+use crate::events::GameEvent;
+use crate::game::Game;
+use crate::player::Bot;
+use crate::scenario;
+use crate::snapshot::Outcome;
+use crate::strategy::{Strategy, StrategyPlayer};
+
+#[derive(Clone, Serialize)]
+pub struct MatchResult {
+    pub seed: u64,
+    pub strategy: String,
+    pub outcome: Option<Outcome>,
+    pub duration: f64,
+    pub time_to_contact: Option<f64>,
+    pub mean_intel_age: Option<f64>,
+    pub mean_order_latency: Option<f64>
+}
+
+#[derive(Serialize)]
+pub struct StrategySummary {
+    pub strategy: String,
+    pub matches: usize,
+    pub victories: usize,
+    pub defeats: usize,
+    pub undecided: usize,
+    pub win_rate: f64,
+    pub contact_rate: f64,
+    pub mean_time_to_contact: Option<f64>,
+    pub mean_intel_age: Option<f64>,
+    pub mean_order_latency: Option<f64>
+}
+
+pub fn run_match(scenario: &scenario::Scenario, config: &config::GameConfig, strategy: Strategy, seed: u64, dt: f64, max_duration: f64) -> MatchResult {
+    let mut game = Game::with_scenario(scenario, config.clone(), seed);
+    let mut bot = Bot::new(StrategyPlayer::new(strategy, seed));
+    let mut time_to_contact = None;
+
+    while !game.is_over() && game.get_time() < max_duration {
+        if bot.is_due(game.get_time()) {
+            let snapshot = game.snapshot();
+            bot.act(game.get_time(), &snapshot, &mut game);
+        }
+        let events = game.update(&dt);
+        if time_to_contact.is_none() && events.iter().any(|event| matches!(event, GameEvent::FightStarted { .. })) {
+            time_to_contact = Some(game.get_time());
+        }
+    }
+
+    MatchResult {
+        seed,
+        strategy: strategy.get_name(),
+        outcome: game.get_outcome(),
+        duration: game.get_time(),
+        time_to_contact,
+        mean_intel_age: get_mean(bot.get_controller().get_intel_ages().iter().copied()),
+        mean_order_latency: game.snapshot().average_order_latency
+    }
+}
+
+pub fn summarize(strategy: Strategy, results: &[MatchResult]) -> StrategySummary {
+    let count_outcome = |outcome: Option<Outcome>| results.iter().filter(|result| result.outcome == outcome).count();
+    let victories = count_outcome(Some(Outcome::Victory));
+    let matches = results.len();
+    let contacts = results.iter().filter(|result| result.time_to_contact.is_some()).count();
+
+    StrategySummary {
+        strategy: strategy.get_name(),
+        matches,
+        victories,
+        defeats: count_outcome(Some(Outcome::Defeat)),
+        undecided: count_outcome(None),
+        win_rate: if matches > 0 { victories as f64 / matches as f64 } else { 0.0 },
+        contact_rate: if matches > 0 { contacts as f64 / matches as f64 } else { 0.0 },
+        mean_time_to_contact: get_mean(results.iter().filter_map(|result| result.time_to_contact)),
+        mean_intel_age: get_mean(results.iter().filter_map(|result| result.mean_intel_age)),
+        mean_order_latency: get_mean(results.iter().filter_map(|result| result.mean_order_latency))
+    }
+}
+
+pub fn format_outcome(outcome: Option<Outcome>) -> &'static str {
+    match outcome {
+        Some(Outcome::Victory) => "victory",
+        Some(Outcome::Defeat) => "defeat",
+        None => "undecided"
+    }
+}
+
+fn get_mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    if count > 0 { Some(sum / count as f64) } else { None }
+}
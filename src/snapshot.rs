@@ -0,0 +1,117 @@
+use std::rc::Rc;
+use serde::Serialize;
+use crate::after_action::Statistics;
+use crate::character;
+use crate::character::CharacterStatus;
+use crate::game::UnitId;
+use crate::exploration::ExploredArea;
+use crate::mission::{MissionEnd, Objective};
+use crate::scout::ScoutStatus;
+use crate::vec2d;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Side {
+    Friendly,
+    Enemy
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum UnitKind {
+    Troops,
+    Scout,
+    Courier
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize)]
+pub enum Outcome {
+    Victory,
+    Defeat
+}
+
+// what is known about one unit; morale and stamina stay hidden for units the viewer can only observe from afar
+#[derive(Clone)]
+pub struct UnitView {
+    pub id: Option<UnitId>,
+    pub side: Side,
+    pub kind: UnitKind,
+    pub position: vec2d::Vec2D,
+    pub target_position: Option<vec2d::Vec2D>,
+    pub status: CharacterStatus,
+    pub scout_status: Option<ScoutStatus>,
+    pub is_carrying_report: bool,
+    pub soldiers: u32,
+    pub max_soldiers: u32,
+    pub morale: Option<f64>,
+    pub stamina: Option<f64>,
+    pub is_surprised: bool,
+    pub view_radius: f64,
+    pub known_enemy_time: Option<f64>
+}
+
+impl UnitView {
+
+    pub fn new(character: &character::Character, id: Option<UnitId>, side: Side, kind: UnitKind, is_fully_known: bool) -> Self {
+        let is_moving = *character.get_status() == CharacterStatus::Moving || *character.get_status() == CharacterStatus::Routing;
+        Self {
+            id,
+            side,
+            kind,
+            position: *character.get_position(),
+            target_position: if is_fully_known && is_moving { Some(*character.get_target_position()) } else { None },
+            status: *character.get_status(),
+            scout_status: None,
+            is_carrying_report: false,
+            soldiers: character.get_soldiers(),
+            max_soldiers: character.get_max_soldiers(),
+            morale: if is_fully_known { Some(*character.get_morale()) } else { None },
+            stamina: if is_fully_known { Some(*character.get_stamina()) } else { None },
+            is_surprised: character.is_surprised(),
+            view_radius: character.get_view_radius(),
+            known_enemy_time: if is_fully_known && character.has_discovered_enemy() { Some(character.get_known_enemy_time()) } else { None }
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct IntelMarker {
+    pub position: vec2d::Vec2D,
+    pub observed_at: f64
+}
+
+// the scout's orders are known even while the scout itself is out of sight; the target is gone once it turns back
+#[derive(Copy, Clone)]
+pub struct ScoutRoute {
+    pub target_position: Option<vec2d::Vec2D>,
+    pub return_position: vec2d::Vec2D
+}
+
+pub struct WorldSnapshot {
+    pub time: f64,
+    pub tick: u64,
+    pub is_omniscient: bool,
+    pub units: Vec<UnitView>,
+    pub enemy_intel: Option<IntelMarker>,
+    pub scout_route: Option<ScoutRoute>,
+    pub orders_in_transit: usize,
+    pub average_order_latency: Option<f64>,
+    // shared with the game rather than copied every frame
+    pub explored_area: Rc<ExploredArea>,
+    pub statistics: Statistics,
+    pub friendly_casualties: u32,
+    pub enemy_casualties: u32,
+    pub fight_radius: f64,
+    pub objective: Objective,
+    pub mission_end: Option<MissionEnd>,
+    pub outcome: Option<Outcome>
+}
+
+impl WorldSnapshot {
+
+    pub fn get_units_of(&self, side: Side) -> impl Iterator<Item = &UnitView> {
+        self.units.iter().filter(move |unit| unit.side == side)
+    }
+
+    pub fn get_unit(&self, id: UnitId) -> Option<&UnitView> {
+        self.units.iter().find(|unit| unit.id == Some(id))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use rand::Rng;
+use rand_pcg::Pcg64;
+use crate::character::CharacterStatus;
+use crate::game::{UnitId, COMMANDER};
+use crate::player::{PlayerCommands, PlayerController};
+use crate::scout::ScoutStatus;
+use crate::snapshot::{Side, UnitKind, WorldSnapshot};
+use crate::vec2d;
+
+const SEARCH_RANGE: f64 = 800.0;
+const NEW_INTEL_DISTANCE: f64 = 50.0;
+// the game draws the enemy spawn from the match seed, the player searches with its own stream of it
+const PLAYER_STREAM: u128 = 0x2545_f491_4f6c_dd1d;
+
+pub const STRATEGY_KINDS: [&str; 3] = ["never-scout", "scout-every", "rush"];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Strategy {
+    NeverScout,
+    ScoutEvery(f64),
+    Rush
+}
+
+impl Strategy {
+
+    // the interval only matters to scout-every
+    pub fn from_kind(kind: &str, scout_interval: f64) -> Option<Strategy> {
+        match kind {
+            "never-scout" => Some(Strategy::NeverScout),
+            "scout-every" => Some(Strategy::ScoutEvery(scout_interval)),
+            "rush" => Some(Strategy::Rush),
+            _ => None
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            Strategy::NeverScout => "never-scout".to_string(),
+            Strategy::ScoutEvery(interval) => format!("scout-every-{interval}s"),
+            Strategy::Rush => "rush".to_string()
+        }
+    }
+}
+
+pub struct StrategyPlayer {
+    strategy: Strategy,
+    rng: Pcg64,
+    next_scout_time: f64,
+    attack_position: Option<vec2d::Vec2D>,
+    intel_ages: Vec<f64>
+}
+
+impl StrategyPlayer {
+
+    pub fn new(strategy: Strategy, seed: u64) -> Self {
+        Self {
+            strategy,
+            rng: Pcg64::new(seed as u128, PLAYER_STREAM),
+            next_scout_time: 0.0,
+            attack_position: None,
+            intel_ages: Vec::new()
+        }
+    }
+
+    // how old the commander's intel was each time it led to an attack order
+    pub fn get_intel_ages(&self) -> &[f64] {
+        &self.intel_ages
+    }
+
+    fn get_search_position(&mut self) -> vec2d::Vec2D {
+        vec2d::Vec2D {
+            x: (self.rng.gen::<f64>() - 0.5) * 2.0 * SEARCH_RANGE,
+            y: (self.rng.gen::<f64>() - 0.5) * 2.0 * SEARCH_RANGE
+        }
+    }
+}
+
+impl PlayerController for StrategyPlayer {
+
+    fn get_name(&self) -> String {
+        self.strategy.get_name()
+    }
+
+    // every strategy attacks once the commander knows where the enemy is; they differ in how they find out
+    fn act(&mut self, snapshot: &WorldSnapshot, commands: &mut dyn PlayerCommands) {
+        if let Some(enemy_intel) = snapshot.enemy_intel {
+            let is_new_intel = self.attack_position
+                .map(|position| (position.x - enemy_intel.position.x).abs() > NEW_INTEL_DISTANCE || (position.y - enemy_intel.position.y).abs() > NEW_INTEL_DISTANCE)
+                .unwrap_or(true);
+            if is_new_intel {
+                self.intel_ages.push(snapshot.time - enemy_intel.observed_at);
+                self.attack_position = Some(enemy_intel.position);
+                order_all_troops(commands, snapshot, &enemy_intel.position);
+            }
+            return;
+        }
+
+        match self.strategy {
+            Strategy::NeverScout => (),
+            Strategy::ScoutEvery(interval) => {
+                let is_scout_idle = snapshot.get_unit(UnitId::Scout).and_then(|scout| scout.scout_status) == Some(ScoutStatus::Idle);
+                if is_scout_idle && snapshot.time >= self.next_scout_time {
+                    let search_position = self.get_search_position();
+                    commands.set_scout_mission(&search_position);
+                    self.next_scout_time = snapshot.time + interval;
+                }
+            }
+            Strategy::Rush => {
+                let is_column_idle = snapshot.get_unit(UnitId::Unit(COMMANDER))
+                    .map(|commander| commander.status == CharacterStatus::Idle)
+                    .unwrap_or(false);
+                if is_column_idle {
+                    let search_position = self.get_search_position();
+                    order_all_troops(commands, snapshot, &search_position);
+                }
+            }
+        }
+    }
+}
+
+fn order_all_troops(commands: &mut dyn PlayerCommands, snapshot: &WorldSnapshot, target_position: &vec2d::Vec2D) {
+    let units: Vec<UnitId> = snapshot.get_units_of(Side::Friendly)
+        .filter(|unit| unit.kind == UnitKind::Troops)
+        .filter_map(|unit| unit.id)
+        .collect();
+    commands.order_units(&units, target_position);
+}
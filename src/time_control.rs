@@ -0,0 +1,66 @@
+const MAX_STEP_DT: f64 = 1.0 / 30.0;
+const SINGLE_STEP_DT: f64 = 1.0 / 60.0;
+const TIME_SCALE_FACTOR: f64 = 2.0;
+const MIN_TIME_SCALE: f64 = 0.125;
+const MAX_TIME_SCALE: f64 = 16.0;
+
+pub struct TimeControl {
+    is_paused: bool,
+    time_scale: f64,
+    pending_steps: u32
+}
+
+impl TimeControl {
+
+    pub fn new() -> Self {
+        Self {
+            is_paused: false,
+            time_scale: 1.0,
+            pending_steps: 0
+        }
+    }
+
+    // splits scaled time into steps no longer than MAX_STEP_DT so fast forward plays out like real time
+    pub fn get_steps(&mut self, real_dt: f64) -> Vec<f64> {
+        if self.is_paused {
+            let steps = vec![SINGLE_STEP_DT; self.pending_steps as usize];
+            self.pending_steps = 0;
+            return steps;
+        }
+        let scaled_dt = real_dt * self.time_scale;
+        let step_count = (scaled_dt / MAX_STEP_DT).ceil().max(1.0);
+        vec![scaled_dt / step_count; step_count as usize]
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.is_paused = !self.is_paused;
+        self.pending_steps = 0;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    // only advances while paused, one fixed tick at a time
+    pub fn step(&mut self) {
+        if self.is_paused {
+            self.pending_steps += 1;
+        }
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        self.time_scale = num::clamp(time_scale, MIN_TIME_SCALE, MAX_TIME_SCALE);
+    }
+
+    pub fn speed_up(&mut self) {
+        self.time_scale = num::clamp(self.time_scale * TIME_SCALE_FACTOR, MIN_TIME_SCALE, MAX_TIME_SCALE);
+    }
+
+    pub fn slow_down(&mut self) {
+        self.time_scale = num::clamp(self.time_scale / TIME_SCALE_FACTOR, MIN_TIME_SCALE, MAX_TIME_SCALE);
+    }
+
+    pub fn get_time_scale(&self) -> f64 {
+        self.time_scale
+    }
+}
@@ -0,0 +1,99 @@
+use piston_window::*;
+use war_fog_experiment::character::{CharacterStatus, MAX_STAMINA};
+use war_fog_experiment::scout::ScoutStatus;
+use war_fog_experiment::snapshot::UnitView;
+use war_fog_experiment::vec2d;
+
+const BAR_WIDTH: f64 = 40.0;
+const BAR_HEIGHT: f64 = 4.0;
+const BAR_SPACING: f64 = 2.0;
+const BAR_MARGIN: f64 = 6.0;
+const ICON_SIZE: f64 = 10.0;
+const BAR_BACKGROUND_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.6];
+const HEALTH_COLOR: [f32; 4] = [0.8, 0.1, 0.1, 1.0];
+const STAMINA_COLOR: [f32; 4] = [0.9, 0.8, 0.1, 1.0];
+const MOVING_COLOR: [f32; 4] = [0.1, 0.7, 0.1, 1.0];
+const FIGHTING_COLOR: [f32; 4] = [0.9, 0.0, 0.0, 1.0];
+const RESTING_COLOR: [f32; 4] = [0.2, 0.4, 0.9, 1.0];
+const ROUTING_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const REPORT_COLOR: [f32; 4] = [0.95, 0.9, 0.75, 1.0];
+const ICON_OUTLINE_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+enum StatusIcon {
+    Moving,
+    Fighting,
+    Resting,
+    Routing
+}
+
+// bars and icons keep the same screen size at every zoom, anchored just above the unit's sprite
+pub fn draw(unit: &UnitView, screen_position: &vec2d::Vec2D, screen_radius: f64, c: &Context, gl: &mut G2d) {
+    let left = screen_position.x - BAR_WIDTH / 2.0;
+    let mut top = screen_position.y - screen_radius - BAR_MARGIN - BAR_HEIGHT;
+
+    if let Some(stamina) = unit.stamina {
+        draw_bar(left, top, stamina / MAX_STAMINA, STAMINA_COLOR, c, gl);
+        top -= BAR_HEIGHT + BAR_SPACING;
+    }
+    let health = if unit.max_soldiers > 0 { unit.soldiers as f64 / unit.max_soldiers as f64 } else { 0.0 };
+    draw_bar(left, top, health, HEALTH_COLOR, c, gl);
+
+    let icon_top = top - BAR_SPACING - ICON_SIZE;
+    let mut icon_left = left;
+    if let Some(icon) = get_status_icon(unit) {
+        draw_status_icon(&icon, icon_left, icon_top, c, gl);
+        icon_left += ICON_SIZE + BAR_SPACING;
+    }
+    if unit.is_carrying_report {
+        draw_report_icon(icon_left, icon_top, c, gl);
+    }
+}
+
+fn draw_bar(left: f64, top: f64, fill: f64, color: [f32; 4], c: &Context, gl: &mut G2d) {
+    rectangle(BAR_BACKGROUND_COLOR, [left, top, BAR_WIDTH, BAR_HEIGHT], c.transform, gl);
+    rectangle(color, [left, top, BAR_WIDTH * num::clamp(fill, 0.0, 1.0), BAR_HEIGHT], c.transform, gl);
+}
+
+fn get_status_icon(unit: &UnitView) -> Option<StatusIcon> {
+    if let Some(scout_status) = unit.scout_status {
+        return if scout_status == ScoutStatus::Idle { Some(StatusIcon::Resting) } else { Some(StatusIcon::Moving) };
+    }
+    match unit.status {
+        CharacterStatus::Moving => Some(StatusIcon::Moving),
+        CharacterStatus::Fighting => Some(StatusIcon::Fighting),
+        CharacterStatus::Idle => Some(StatusIcon::Resting),
+        CharacterStatus::Routing => Some(StatusIcon::Routing),
+        CharacterStatus::Dead => None
+    }
+}
+
+fn draw_status_icon(icon: &StatusIcon, left: f64, top: f64, c: &Context, gl: &mut G2d) {
+    match icon {
+        StatusIcon::Moving => {
+            let arrow = [[0.0, 0.0], [ICON_SIZE, ICON_SIZE / 2.0], [0.0, ICON_SIZE]];
+            polygon(MOVING_COLOR, &arrow, c.transform.trans(left, top), gl);
+        }
+        StatusIcon::Fighting => {
+            let sword = Line::new(FIGHTING_COLOR, 1.5);
+            sword.draw([left, top, left + ICON_SIZE, top + ICON_SIZE], &DrawState::default(), c.transform, gl);
+            sword.draw([left + ICON_SIZE, top, left, top + ICON_SIZE], &DrawState::default(), c.transform, gl);
+        }
+        StatusIcon::Resting => {
+            ellipse(RESTING_COLOR, [left, top, ICON_SIZE, ICON_SIZE], c.transform, gl);
+        }
+        StatusIcon::Routing => {
+            let arrow = [[0.0, 0.0], [ICON_SIZE, 0.0], [ICON_SIZE / 2.0, ICON_SIZE]];
+            polygon(ROUTING_COLOR, &arrow, c.transform.trans(left, top), gl);
+        }
+    }
+}
+
+// a small envelope
+fn draw_report_icon(left: f64, top: f64, c: &Context, gl: &mut G2d) {
+    let envelope = [left, top + ICON_SIZE * 0.2, ICON_SIZE * 1.2, ICON_SIZE * 0.8];
+    rectangle(REPORT_COLOR, envelope, c.transform, gl);
+    Rectangle::new_border(ICON_OUTLINE_COLOR, 0.5).draw(envelope, &DrawState::default(), c.transform, gl);
+    let flap = Line::new(ICON_OUTLINE_COLOR, 0.5);
+    flap.draw([envelope[0], envelope[1], envelope[0] + envelope[2] / 2.0, envelope[1] + envelope[3] / 2.0], &DrawState::default(), c.transform, gl);
+    flap.draw([envelope[0] + envelope[2], envelope[1], envelope[0] + envelope[2] / 2.0, envelope[1] + envelope[3] / 2.0], &DrawState::default(), c.transform, gl);
+}
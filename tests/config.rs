@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+use war_fog_experiment::config::{ConfigError, GameConfig};
+
+// an empty file keeps every default, so the tests do not depend on the config.json next to them
+fn write_empty_config(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, "{}").unwrap();
+    path
+}
+
+#[test]
+fn override_sets_the_named_setting() {
+    let mut config = GameConfig::default();
+    config.apply_override(" view_radius = 250.5 ").unwrap();
+    config.apply_override("window_width=800").unwrap();
+    assert_eq!(config.view_radius, 250.5);
+    assert_eq!(config.window_width, 800);
+}
+
+#[test]
+fn malformed_overrides_are_rejected() {
+    let mut config = GameConfig::default();
+    assert!(matches!(config.apply_override("view_radius"), Err(ConfigError::InvalidOverride(_))));
+    assert!(matches!(config.apply_override("sight=100"), Err(ConfigError::UnknownSetting(name)) if name == "sight"));
+    assert!(matches!(config.apply_override("view_radius=far"), Err(ConfigError::Format(_))));
+    assert_eq!(config, GameConfig::default());
+}
+
+#[test]
+fn settings_that_break_the_game_are_rejected() {
+    let path = write_empty_config("war-fog-config-rejected-values.json");
+    let rejected = [
+        vec!["view_radius=0".to_string()],
+        vec!["character_speed=-50".to_string()],
+        vec!["window_height=0".to_string()],
+        vec!["enemy_rest_stamina=40".to_string(), "enemy_chase_stamina=60".to_string()]
+    ];
+    for overrides in rejected.iter() {
+        let result = GameConfig::load_with_overrides(Some(&path), overrides);
+        assert!(matches!(result, Err(ConfigError::InvalidValue { .. })), "{overrides:?} was accepted");
+    }
+
+    let config = GameConfig::load_with_overrides(Some(&path), &["enemy_rest_stamina=80".to_string(), "enemy_chase_stamina=60".to_string()]).unwrap();
+    assert_eq!(config.enemy_chase_stamina, 60.0);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn config_file_with_a_broken_value_is_rejected() {
+    let path = std::env::temp_dir().join("war-fog-config-broken-file.json");
+    std::fs::write(&path, r#"{ "fight_radius": 0.0 }"#).unwrap();
+    let result = GameConfig::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(result, Err(ConfigError::InvalidValue { name: "fight_radius", .. })));
+}
@@ -0,0 +1,129 @@
+use war_fog_experiment::config::GameConfig;
+use war_fog_experiment::events::GameEvent;
+use war_fog_experiment::game::{Game, UnitId, COMMANDER};
+use war_fog_experiment::save;
+use war_fog_experiment::scenario;
+use war_fog_experiment::snapshot::Side;
+use war_fog_experiment::vec2d;
+
+const DT: f64 = 0.05;
+const MAX_DURATION: f64 = 600.0;
+
+fn get_enemy_position(game: &Game) -> vec2d::Vec2D {
+    game.omniscient_snapshot().get_units_of(Side::Enemy).next().unwrap().position
+}
+
+#[test]
+fn enemy_is_sighted_before_the_fight_and_the_fight_before_the_end() {
+    let scenario = scenario::find_scenario("skirmish").unwrap();
+    let mut game = Game::with_scenario(scenario, GameConfig::default(), 7);
+    let mut events = Vec::new();
+
+    // march the commander straight at the enemy until the game ends
+    while game.get_time() < MAX_DURATION && game.get_outcome().is_none() {
+        let enemy_position = get_enemy_position(&game);
+        game.order_unit(UnitId::Unit(COMMANDER), &enemy_position);
+        events.extend(game.update(&DT));
+    }
+
+    let sighted = events.iter().position(|event| matches!(event, GameEvent::EnemySighted { .. })).expect("the enemy was never sighted");
+    let fight = events.iter().position(|event| matches!(event, GameEvent::FightStarted { .. })).expect("no fight started");
+    let game_over = events.iter().position(|event| matches!(event, GameEvent::GameOver { .. })).expect("the game never ended");
+    assert!(sighted < fight);
+    assert!(fight < game_over);
+}
+
+#[test]
+fn courier_to_a_detached_unit_is_only_reported_by_the_next_update() {
+    let scenario = scenario::find_scenario("skirmish").unwrap();
+    let mut game = Game::with_scenario(scenario, GameConfig::default(), 7);
+
+    // take the commander out of earshot of the detachments first
+    game.order_unit(UnitId::Unit(COMMANDER), &vec2d::Vec2D{ x: 0.0, y: -1000.0 });
+    while game.get_time() < 60.0 {
+        let events = game.update(&DT);
+        assert!(!events.iter().any(|event| matches!(event, GameEvent::CourierDispatched { sender: UnitId::Unit(COMMANDER), .. })));
+    }
+
+    game.order_unit(UnitId::Unit(1), &vec2d::Vec2D{ x: 0.0, y: 500.0 });
+    let events = game.update(&DT);
+    assert!(events.contains(&GameEvent::CourierDispatched { sender: UnitId::Unit(COMMANDER), recipient: UnitId::Unit(1) }));
+    assert_eq!(game.snapshot().orders_in_transit, 1);
+}
+
+#[test]
+fn marching_onto_a_resting_enemy_with_intel_is_no_ambush() {
+    let scenario = scenario::find_scenario("lone-column").unwrap();
+    // the enemy never stops resting and never chases
+    let config = GameConfig { enemy_rest_stamina: 1000.0, enemy_chase_stamina: 1000.0, ..GameConfig::default() };
+
+    for seed in 0..20 {
+        let mut game = Game::with_scenario(scenario, config.clone(), seed);
+        let enemy_position = get_enemy_position(&game);
+        game.order_unit(UnitId::Scout, &enemy_position);
+        while game.get_time() < MAX_DURATION && game.snapshot().enemy_intel.is_none() {
+            game.update(&DT);
+        }
+        let enemy_intel = game.snapshot().enemy_intel.expect("the scout never reported the enemy");
+
+        game.order_unit(UnitId::Unit(COMMANDER), &enemy_intel.position);
+        let mut events = Vec::new();
+        while game.get_time() < MAX_DURATION && !events.iter().any(|event| matches!(event, GameEvent::FightStarted { .. })) {
+            events.extend(game.update(&DT));
+        }
+        assert!(events.iter().any(|event| matches!(event, GameEvent::FightStarted { .. })), "seed {seed}: the column never reached the enemy");
+        assert!(!events.iter().any(|event| matches!(event, GameEvent::Ambushed { side: Side::Friendly, .. })), "seed {seed}: the column was ambushed");
+    }
+}
+
+#[test]
+fn intel_reaches_a_weakened_commander_from_a_stronger_detachment() {
+    let scenario = scenario::find_scenario("skirmish").unwrap();
+    let game = Game::with_scenario(scenario, GameConfig::default(), 7);
+    let enemy_position = get_enemy_position(&game);
+
+    // the commander down to 40 men sees less far than the full-strength unit 1 standing 170 away with intel
+    let path = std::env::temp_dir().join("war-fog-intel-to-weakened-commander.json");
+    save::save_game(&game, &path).unwrap();
+    // edited as text, the rng state does not survive a round trip through serde_json::Value
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let unit_1_start = contents.find(r#""position":{"x":-150.0,"y":100.0}"#).unwrap();
+    let (commander, unit_1) = contents.split_at(unit_1_start);
+    let commander = commander.replacen(r#""soldiers":100,"max_soldiers":100"#, r#""soldiers":40,"max_soldiers":100"#, 1);
+    let unit_1 = unit_1
+        .replacen(r#""position":{"x":-150.0,"y":100.0}"#, r#""position":{"x":170.0,"y":0.0}"#, 1)
+        .replacen(r#""known_enemy_position":{"x":0.0,"y":0.0}"#, &format!(r#""known_enemy_position":{{"x":{:?},"y":{:?}}}"#, enemy_position.x, enemy_position.y), 1)
+        .replacen(r#""has_discovered_enemy":false"#, r#""has_discovered_enemy":true"#, 1);
+    std::fs::write(&path, commander + &unit_1).unwrap();
+    let mut game = save::load_game(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    while game.get_time() < 60.0 && game.snapshot().enemy_intel.is_none() {
+        game.update(&DT);
+    }
+    assert!(game.snapshot().enemy_intel.is_some());
+}
+
+#[test]
+fn scout_sweep_is_only_explored_once_the_scout_is_back() {
+    let scenario = scenario::find_scenario("lone-column").unwrap();
+    let mut game = Game::with_scenario(scenario, GameConfig::default(), 7);
+    let far_position = vec2d::Vec2D{ x: 900.0, y: 0.0 };
+    game.order_unit(UnitId::Scout, &far_position);
+
+    let mut events = Vec::new();
+    while game.get_time() < MAX_DURATION && !events.iter().any(|event| matches!(event, GameEvent::ScoutReturned { .. })) {
+        assert!(!game.snapshot().explored_area.is_explored(&far_position));
+        events.extend(game.update(&DT));
+    }
+    assert!(game.snapshot().explored_area.is_explored(&far_position));
+}
+
+#[test]
+fn one_order_to_several_units_is_one_decision() {
+    let scenario = scenario::find_scenario("skirmish").unwrap();
+    let mut game = Game::with_scenario(scenario, GameConfig::default(), 7);
+    game.order_units(&[UnitId::Unit(0), UnitId::Unit(1), UnitId::Unit(2)], &vec2d::Vec2D{ x: 0.0, y: 300.0 });
+    game.order_unit(UnitId::Scout, &vec2d::Vec2D{ x: 300.0, y: 0.0 });
+    assert_eq!(game.snapshot().statistics.orders_given, 2);
+}
@@ -0,0 +1,38 @@
+use war_fog_experiment::config::GameConfig;
+use war_fog_experiment::game::Game;
+use war_fog_experiment::save::{self, SaveError};
+use war_fog_experiment::scenario;
+
+#[test]
+fn saved_game_loads_back_the_same() {
+    let mut game = Game::with_scenario(scenario::get_default_scenario(), GameConfig::default(), 3);
+    for _ in 0..100 {
+        game.update(&0.05);
+    }
+
+    let path = std::env::temp_dir().join("war-fog-save-round-trip.json");
+    save::save_game(&game, &path).unwrap();
+    let loaded_game = save::load_game(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded_game.get_tick(), game.get_tick());
+    assert_eq!(loaded_game.omniscient_snapshot().units.iter().map(|unit| unit.position).collect::<Vec<_>>(),
+        game.omniscient_snapshot().units.iter().map(|unit| unit.position).collect::<Vec<_>>());
+}
+
+#[test]
+fn save_from_another_format_version_is_rejected() {
+    let path = std::env::temp_dir().join("war-fog-save-old-version.json");
+    std::fs::write(&path, r#"{"version":0,"game":{}}"#).unwrap();
+    let result = save::load_game(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    match result {
+        Err(SaveError::VersionMismatch { found, expected }) => {
+            assert_eq!(found, 0);
+            assert_ne!(expected, 0);
+        }
+        Err(error) => panic!("wrong error: {error}"),
+        Ok(_) => panic!("a save from another version was loaded")
+    }
+}
@@ -0,0 +1,48 @@
+use war_fog_experiment::config::GameConfig;
+use war_fog_experiment::game::{Game, UnitId};
+use war_fog_experiment::player::{PlayerCommands, PlayerController};
+use war_fog_experiment::scenario;
+use war_fog_experiment::snapshot::Side;
+use war_fog_experiment::strategy::{Strategy, StrategyPlayer};
+use war_fog_experiment::vec2d;
+
+const SEEDS: u64 = 50;
+
+#[derive(Default)]
+struct RecordedCommands {
+    targets: Vec<vec2d::Vec2D>
+}
+
+impl PlayerCommands for RecordedCommands {
+
+    fn order_units(&mut self, _units: &[UnitId], target_position: &vec2d::Vec2D) {
+        self.targets.push(*target_position);
+    }
+
+    fn set_scout_mission(&mut self, target_position: &vec2d::Vec2D) {
+        self.targets.push(*target_position);
+    }
+}
+
+// the sine of the angle between both positions seen from the centre of the map
+fn get_direction_difference(a: &vec2d::Vec2D, b: &vec2d::Vec2D) -> f64 {
+    (a.x * b.y - a.y * b.x).abs() / ((a.x.powi(2) + a.y.powi(2)).sqrt() * (b.x.powi(2) + b.y.powi(2)).sqrt())
+}
+
+#[test]
+fn first_search_target_does_not_follow_the_enemy_spawn() {
+    let scenario = scenario::find_scenario("outnumbered").unwrap();
+    for strategy in [Strategy::Rush, Strategy::ScoutEvery(30.0)] {
+        for seed in 0..SEEDS {
+            let game = Game::with_scenario(scenario, GameConfig::default(), seed);
+            let enemy_spawn = game.omniscient_snapshot().get_units_of(Side::Enemy).next().unwrap().position;
+
+            let mut player = StrategyPlayer::new(strategy, seed);
+            let mut commands = RecordedCommands::default();
+            player.act(&game.snapshot(), &mut commands);
+
+            let target = commands.targets.first().expect("the player gave no order");
+            assert!(get_direction_difference(target, &enemy_spawn) > 1e-6, "seed {seed}: {strategy:?} searched towards the enemy spawn");
+        }
+    }
+}